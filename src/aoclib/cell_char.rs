@@ -0,0 +1,134 @@
+use std::fmt;
+
+use super::{DenseGrid, GridParseError};
+
+/// A grid cell that's written as a single character in puzzle input.
+///
+/// `from_char` and `to_char` should be inverses, so that `DenseGrid::parse` and the grid's
+/// `Display` impl round-trip. For fieldless enums, `cell_chars!` writes both from one table.
+pub trait CellChar: Sized {
+    /// The cell for a character, or None if it isn't one.
+    fn from_char(c: char) -> Option<Self>;
+
+    fn to_char(&self) -> char;
+}
+
+impl CellChar for char {
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// Implement `CellChar` for a fieldless enum from a single `Variant => 'c'` table.
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Cell {
+///     Wall,
+///     Empty,
+/// }
+///
+/// aoclib::cell_chars! {
+///     Cell {
+///         Wall => '#',
+///         Empty => '.',
+///     }
+/// }
+///
+/// use aoclib::CellChar;
+/// assert_eq!(Cell::from_char('#'), Some(Cell::Wall));
+/// assert_eq!(Cell::Empty.to_char(), '.');
+/// ```
+#[macro_export]
+macro_rules! cell_chars {
+    ($ty:ty { $($variant:ident => $c:literal),+ $(,)? }) => {
+        impl $crate::CellChar for $ty {
+            fn from_char(c: char) -> Option<Self> {
+                match c {
+                    $($c => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+
+            fn to_char(&self) -> char {
+                match self {
+                    $(Self::$variant => $c,)+
+                }
+            }
+        }
+    };
+}
+
+impl<V: Clone + fmt::Debug + CellChar> DenseGrid<V> {
+    /// Parse a grid of `CellChar`s; see `parse_with` for what's accepted.
+    pub fn parse(input: &str) -> Result<Self, GridParseError> {
+        Ok(Self::parse_with(input, &[], V::from_char)?.grid)
+    }
+}
+
+/// Writes one line per row, without a trailing newline, in the format `DenseGrid::parse`
+/// reads.
+impl<V: Clone + fmt::Debug + CellChar> fmt::Display for DenseGrid<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in self.row_numbers() {
+            if y != self.origin().y {
+                writeln!(f)?;
+            }
+            for cell in self.row(y).expect("row is in bounds") {
+                write!(f, "{}", cell.to_char())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CellChar;
+    use crate::{DenseGrid, GridParseError, Point};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Cell {
+        Wall,
+        Empty,
+        Start,
+    }
+
+    cell_chars! {
+        Cell {
+            Wall => '#',
+            Empty => '.',
+            Start => 'S',
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for cell in [Cell::Wall, Cell::Empty, Cell::Start] {
+            assert_eq!(Cell::from_char(cell.to_char()), Some(cell));
+        }
+        assert_eq!(Cell::from_char('x'), None);
+
+        let input = "#####\n#S..#\n#####";
+        let g = DenseGrid::<Cell>::parse(input).unwrap();
+        assert_eq!(g[Point::new(1, 1)], Cell::Start);
+        assert_eq!(g.to_string(), input);
+        assert_eq!(DenseGrid::<Cell>::parse(&g.to_string()).unwrap(), g);
+        assert_eq!(
+            DenseGrid::<Cell>::parse("#.\n#x").unwrap_err(),
+            GridParseError::UnexpectedChar {
+                line: 2,
+                column: 2,
+                found: 'x'
+            }
+        );
+        assert_eq!(
+            DenseGrid::<char>::parse("ab\ncd\n").unwrap().to_string(),
+            "ab\ncd"
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use petgraph::graph::{EdgeIndex, NodeIndex, UnGraph};
+
+use super::point::Point;
+use super::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CorridorOptions {
+    /// Repeatedly fill in passable cells with only one passable neighbor, so corridors that
+    /// lead nowhere disappear from the graph. Kept cells are never pruned.
+    pub prune_dead_ends: bool,
+    /// Also treat corners as junctions, so every edge is a straight line.
+    pub split_at_turns: bool,
+}
+
+/// A maze reduced to its decision points.
+///
+/// Nodes are junction cells; edges are the corridors between them, weighted by their length
+/// in steps.
+#[derive(Debug, Clone)]
+pub struct JunctionGraph {
+    pub graph: UnGraph<Point, usize>,
+    pub nodes: BTreeMap<Point, NodeIndex>,
+    edge_cells: Vec<Vec<Point>>,
+}
+
+impl JunctionGraph {
+    /// Contract the passable cells of `grid`. A cell becomes a junction if it doesn't have
+    /// exactly two passable neighbors, if `keep` says so, or (with `split_at_turns`) if it's a
+    /// corner. A closed loop with no junction on it gets its first cell as a node, joined to
+    /// itself by an edge around the loop.
+    ///
+    /// Neighbors come from `Grid::neighbors`, so corridors follow the grid's wrapping.
+    pub fn build<G, PF, KF>(grid: &G, passable: PF, keep: KF, options: CorridorOptions) -> Self
+    where
+        G: Grid,
+        PF: Fn(&G, Point) -> bool,
+        KF: Fn(&G, Point) -> bool,
+    {
+        let mut open = grid
+            .points()
+            .filter(|p| passable(grid, *p))
+            .collect::<BTreeSet<_>>();
+        let kept = open
+            .iter()
+            .copied()
+            .filter(|p| keep(grid, *p))
+            .collect::<BTreeSet<_>>();
+        let open_neighbors = |open: &BTreeSet<Point>, p: Point| {
+            grid.neighbors(p)
+                .filter(|n| open.contains(n))
+                .collect::<Vec<_>>()
+        };
+
+        if options.prune_dead_ends {
+            let mut work = open.iter().copied().collect::<Vec<_>>();
+            while let Some(point) = work.pop() {
+                if kept.contains(&point) || !open.contains(&point) {
+                    continue;
+                }
+                let neighbors = open_neighbors(&open, point);
+                if neighbors.len() <= 1 {
+                    open.remove(&point);
+                    work.extend(neighbors);
+                }
+            }
+        }
+
+        let is_junction = |p: Point| {
+            let neighbors = open_neighbors(&open, p);
+            if neighbors.len() != 2 || kept.contains(&p) {
+                return true;
+            }
+            options.split_at_turns && neighbors[0] - p != p - neighbors[1]
+        };
+
+        let mut graph = UnGraph::new_undirected();
+        let mut nodes = BTreeMap::new();
+        for point in open.iter().copied().filter(|p| is_junction(*p)) {
+            nodes.insert(point, graph.add_node(point));
+        }
+
+        let mut edge_cells = vec![];
+        let mut walked = BTreeSet::new();
+        let mut covered = BTreeSet::new();
+        let mut pending = nodes.keys().copied().collect::<Vec<_>>();
+        loop {
+            for start in pending.drain(..) {
+                covered.insert(start);
+                for first in open_neighbors(&open, start) {
+                    if walked.contains(&(start, first)) {
+                        continue;
+                    }
+                    let mut cells = vec![start, first];
+                    let mut prev = start;
+                    let mut current = first;
+                    while !nodes.contains_key(&current) {
+                        let next = open_neighbors(&open, current)
+                            .into_iter()
+                            .find(|n| *n != prev)
+                            .expect("corridor cells have two neighbors");
+                        prev = current;
+                        current = next;
+                        cells.push(current);
+                    }
+                    walked.insert((start, first));
+                    walked.insert((current, prev));
+                    graph.add_edge(nodes[&start], nodes[&current], cells.len() - 1);
+                    covered.extend(cells.iter().copied());
+                    edge_cells.push(cells);
+                }
+            }
+            // Whatever no corridor reached is a loop without a junction on it.
+            let Some(orphan) = open.iter().copied().find(|p| !covered.contains(p)) else {
+                break;
+            };
+            nodes.insert(orphan, graph.add_node(orphan));
+            pending.push(orphan);
+        }
+
+        JunctionGraph {
+            graph,
+            nodes,
+            edge_cells,
+        }
+    }
+
+    /// The cells an edge runs through, from its source junction to its target, inclusive.
+    pub fn cells_for(&self, edge: EdgeIndex) -> &[Point] {
+        &self.edge_cells[edge.index()]
+    }
+
+    pub fn node_for(&self, point: Point) -> Option<NodeIndex> {
+        self.nodes.get(&point).copied()
+    }
+
+    pub fn point_for(&self, node: NodeIndex) -> Point {
+        self.graph[node]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CorridorOptions;
+    use crate::{DenseGrid, Grid, Point};
+
+    const MAZE: &str = "#######
+#S....#
+#.###.#
+#.#...#
+#.#.#.#
+#...#E#
+#######";
+
+    #[test]
+    fn test_contract() {
+        let g = DenseGrid::from_input(MAZE, |c| c);
+        let passable = |g: &DenseGrid<char>, p| g.get(p) != Some('#');
+        let keep = |g: &DenseGrid<char>, p| matches!(g.get(p), Some('S') | Some('E'));
+
+        let j = g.contract_corridors(passable, keep, CorridorOptions::default());
+        // S, E, and the T where E's dead end branches off the loop
+        assert_eq!(j.graph.node_count(), 3);
+        assert_eq!(j.graph.edge_count(), 3);
+        let total: usize = j.graph.edge_weights().sum();
+        assert_eq!(total, 18);
+        for edge in j.graph.edge_indices() {
+            let cells = j.cells_for(edge);
+            assert_eq!(cells.len() - 1, j.graph[edge]);
+            let (a, b) = j.graph.edge_endpoints(edge).unwrap();
+            assert_eq!(j.point_for(a), cells[0]);
+            assert_eq!(j.point_for(b), *cells.last().unwrap());
+        }
+
+        let keep_start = |g: &DenseGrid<char>, p| g.get(p) == Some('S');
+        let pruned = g.contract_corridors(
+            passable,
+            keep_start,
+            CorridorOptions {
+                prune_dead_ends: true,
+                split_at_turns: false,
+            },
+        );
+        // E's branch is pruned, so only the loop through S remains
+        assert_eq!(pruned.graph.node_count(), 1);
+        assert_eq!(pruned.graph.edge_count(), 1);
+        assert_eq!(pruned.graph.edge_weights().sum::<usize>(), 16);
+        assert!(pruned.node_for(Point::new(5, 5)).is_none());
+
+        let straight = g.contract_corridors(
+            passable,
+            keep_start,
+            CorridorOptions {
+                prune_dead_ends: true,
+                split_at_turns: true,
+            },
+        );
+        assert_eq!(straight.graph.node_count(), 6);
+        assert!(straight.graph.edge_indices().all(|e| {
+            let cells = straight.cells_for(e);
+            cells.iter().all(|c| c.x == cells[0].x) || cells.iter().all(|c| c.y == cells[0].y)
+        }));
+    }
+
+    #[test]
+    fn test_loops() {
+        let passable = |g: &DenseGrid<char>, p| g.get(p) == Some('.');
+        let never = |_: &DenseGrid<char>, _| false;
+
+        let g = DenseGrid::from_input("....\n.##.\n....", |c| c);
+        let j = g.contract_corridors(passable, never, CorridorOptions::default());
+        assert_eq!(j.graph.node_count(), 1);
+        assert!(j.node_for(Point::new(0, 0)).is_some());
+        assert_eq!(j.graph.edge_weights().collect::<Vec<_>>(), vec![&10]);
+
+        // Each row is a corridor that only closes up by wrapping around.
+        let ring = DenseGrid::from_input("....\n####\n....\n####", |c| c).with_wrapping();
+        let j = ring.contract_corridors(passable, never, CorridorOptions::default());
+        assert_eq!(j.graph.node_count(), 2);
+        assert_eq!(j.graph.edge_weights().sum::<usize>(), 8);
+    }
+}
@@ -26,3 +26,8 @@ impl<
     > DimVal for S
 {
 }
+
+/// `from..=to` for any `DimVal`, counting up by one.
+pub(crate) fn inclusive_range<I: DimVal>(from: I, to: I) -> impl Iterator<Item = I> {
+    std::iter::successors(Some(from), |v| Some(*v + I::one())).take_while(move |v| *v <= to)
+}
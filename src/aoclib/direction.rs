@@ -0,0 +1,377 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::point::{Point, Rotation};
+
+/// One of the four orthogonal directions on a grid where y increases downwards.
+///
+/// `ALL` (and the discriminants) run clockwise starting from East, the same order as
+/// `Point::ordinal_neighbors` and `VonNeumann::OFFSETS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    East,
+    South,
+    West,
+    North,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::East,
+        Direction::South,
+        Direction::West,
+        Direction::North,
+    ];
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    pub fn turn_cw(&self) -> Self {
+        Self::from_index(*self as usize + 1)
+    }
+
+    pub fn turn_ccw(&self) -> Self {
+        Self::from_index(*self as usize + 3)
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::from_index(*self as usize + 2)
+    }
+
+    /// Turn the same way `Point::rotate_by` would rotate this direction's vector.
+    pub fn rotate_by(&self, r: Rotation) -> Self {
+        match r {
+            Rotation::CW => self.turn_cw(),
+            Rotation::CCW => self.turn_ccw(),
+            Rotation::Mirror => self.reverse(),
+        }
+    }
+
+    /// The fewest quarter turns (in either direction) to face `other`.
+    pub fn turns_to(&self, other: Direction) -> usize {
+        let cw = (other as usize + 4 - *self as usize) % 4;
+        cw.min(4 - cw)
+    }
+
+    pub fn as_vector(&self) -> Point {
+        match self {
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+            Direction::North => Point::new(0, -1),
+        }
+    }
+
+    /// The direction of a unit orthogonal step, or None for anything else.
+    pub fn from_vector(vector: Point) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.as_vector() == vector)
+    }
+
+    /// The direction to step from `from` to reach the orthogonally adjacent `to`.
+    pub fn between(from: Point, to: Point) -> Option<Self> {
+        Self::from_vector(to - from)
+    }
+
+    /// Parse any of `^>v<`, `NESW` or `UDLR`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '>' | 'E' | 'R' => Some(Direction::East),
+            'v' | 'S' | 'D' => Some(Direction::South),
+            '<' | 'W' | 'L' => Some(Direction::West),
+            '^' | 'N' | 'U' => Some(Direction::North),
+            _ => None,
+        }
+    }
+
+    /// Parse only `^>v<`, for input where letters would be an error.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.to_arrow() == c)
+    }
+
+    pub fn to_arrow(&self) -> char {
+        ['>', 'v', '<', '^'][*self as usize]
+    }
+
+    pub fn to_compass(&self) -> char {
+        ['E', 'S', 'W', 'N'][*self as usize]
+    }
+
+    pub fn to_udlr(&self) -> char {
+        ['R', 'D', 'L', 'U'][*self as usize]
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(d: Direction) -> Self {
+        d.as_vector()
+    }
+}
+
+impl TryFrom<Point> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(vector: Point) -> Result<Self, Self::Error> {
+        Self::from_vector(vector).ok_or_else(|| anyhow::anyhow!("{} is not a unit step", vector))
+    }
+}
+
+impl std::ops::Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, d: Direction) -> Point {
+        self + d.as_vector()
+    }
+}
+
+impl FromStr for Direction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next().and_then(Self::from_char), chars.next()) {
+            (Some(d), None) => Ok(d),
+            _ => anyhow::bail!("unhandled direction {:?}", s),
+        }
+    }
+}
+
+/// Displays as an arrow; use `to_compass` or `to_udlr` for the other spellings.
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_arrow())
+    }
+}
+
+/// One of the four orthogonal or four diagonal directions.
+///
+/// `ALL` (and the discriminants) run clockwise starting from East, the same order as
+/// `Moore::OFFSETS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+    North,
+    NorthEast,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+        Direction8::North,
+        Direction8::NorthEast,
+    ];
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    /// Turn 45° clockwise.
+    pub fn turn_cw(&self) -> Self {
+        Self::from_index(*self as usize + 1)
+    }
+
+    /// Turn 45° counterclockwise.
+    pub fn turn_ccw(&self) -> Self {
+        Self::from_index(*self as usize + 7)
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::from_index(*self as usize + 4)
+    }
+
+    /// Turn the same way `Point::rotate_by` would rotate this direction's vector; that is, by
+    /// a quarter turn rather than the 45° of `turn_cw`.
+    pub fn rotate_by(&self, r: Rotation) -> Self {
+        match r {
+            Rotation::CW => Self::from_index(*self as usize + 2),
+            Rotation::CCW => Self::from_index(*self as usize + 6),
+            Rotation::Mirror => self.reverse(),
+        }
+    }
+
+    /// The fewest 45° turns (in either direction) to face `other`.
+    pub fn turns_to(&self, other: Direction8) -> usize {
+        let cw = (other as usize + 8 - *self as usize) % 8;
+        cw.min(8 - cw)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        (*self as usize) % 2 == 1
+    }
+
+    /// The equivalent orthogonal direction, if this isn't a diagonal.
+    pub fn as_orthogonal(&self) -> Option<Direction> {
+        if self.is_diagonal() {
+            None
+        } else {
+            Some(Direction::ALL[*self as usize / 2])
+        }
+    }
+
+    pub fn as_vector(&self) -> Point {
+        match self {
+            Direction8::East => Point::new(1, 0),
+            Direction8::SouthEast => Point::new(1, 1),
+            Direction8::South => Point::new(0, 1),
+            Direction8::SouthWest => Point::new(-1, 1),
+            Direction8::West => Point::new(-1, 0),
+            Direction8::NorthWest => Point::new(-1, -1),
+            Direction8::North => Point::new(0, -1),
+            Direction8::NorthEast => Point::new(1, -1),
+        }
+    }
+
+    /// The direction of a step to any of the eight neighboring cells, or None for anything
+    /// else.
+    pub fn from_vector(vector: Point) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.as_vector() == vector)
+    }
+
+    pub fn between(from: Point, to: Point) -> Option<Self> {
+        Self::from_vector(to - from)
+    }
+
+    /// The compass abbreviation (`N`, `NE`, `E`, ...).
+    pub fn to_compass(&self) -> &'static str {
+        ["E", "SE", "S", "SW", "W", "NW", "N", "NE"][*self as usize]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        Self::ALL[d as usize * 2]
+    }
+}
+
+impl From<Direction8> for Point {
+    fn from(d: Direction8) -> Self {
+        d.as_vector()
+    }
+}
+
+impl TryFrom<Point> for Direction8 {
+    type Error = anyhow::Error;
+
+    fn try_from(vector: Point) -> Result<Self, Self::Error> {
+        Self::from_vector(vector).ok_or_else(|| anyhow::anyhow!("{} is not a unit step", vector))
+    }
+}
+
+impl std::ops::Add<Direction8> for Point {
+    type Output = Point;
+
+    fn add(self, d: Direction8) -> Point {
+        self + d.as_vector()
+    }
+}
+
+/// Parses compass abbreviations, plus anything `Direction` accepts.
+impl FromStr for Direction8 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(d) = Self::ALL.into_iter().find(|d| d.to_compass() == s) {
+            return Ok(d);
+        }
+        s.parse::<Direction>().map(Self::from)
+    }
+}
+
+impl fmt::Display for Direction8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_compass())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Direction8};
+    use crate::{Moore, Point, Rotation};
+
+    #[test]
+    fn test_turns() {
+        use Direction::*;
+        assert_eq!(North.turn_cw(), East);
+        assert_eq!(North.turn_ccw(), West);
+        assert_eq!(East.reverse(), West);
+        assert_eq!(North.turns_to(South), 2);
+        assert_eq!(West.turns_to(North), 1);
+        assert_eq!(North.turns_to(West), 1);
+        assert_eq!(South.turns_to(South), 0);
+        for d in Direction::ALL {
+            for r in [Rotation::CW, Rotation::CCW, Rotation::Mirror] {
+                assert_eq!(d.rotate_by(r).as_vector(), d.as_vector().rotate_by(r));
+            }
+        }
+    }
+
+    #[test]
+    fn test_vectors() {
+        let p = Point::new(3, 4);
+        let neighbors = Direction::ALL.map(|d| p + d);
+        assert_eq!(neighbors, p.ordinal_neighbors_array());
+        assert_eq!(
+            Direction::between(p, p + Direction::North),
+            Some(Direction::North)
+        );
+        assert_eq!(Direction::between(p, Point::new(4, 5)), None);
+        assert_eq!(
+            Direction::try_from(Point::new(0, 1)).unwrap(),
+            Direction::South
+        );
+        assert!(Direction::try_from(Point::new(0, 2)).is_err());
+        assert_eq!(Point::from(Direction::West), Point::new(-1, 0));
+    }
+
+    #[test]
+    fn test_parsing() {
+        for (arrow, compass, udlr) in [
+            ('^', 'N', 'U'),
+            ('>', 'E', 'R'),
+            ('v', 'S', 'D'),
+            ('<', 'W', 'L'),
+        ] {
+            let d = Direction::from_char(arrow).unwrap();
+            assert_eq!(Direction::from_char(compass), Some(d));
+            assert_eq!(Direction::from_char(udlr), Some(d));
+            assert_eq!(Direction::from_arrow(arrow), Some(d));
+            assert_eq!(Direction::from_arrow(compass), None);
+            assert_eq!(Direction::from_arrow(udlr), None);
+            assert_eq!(d.to_arrow(), arrow);
+            assert_eq!(d.to_compass(), compass);
+            assert_eq!(d.to_udlr(), udlr);
+            assert_eq!(d.to_string().parse::<Direction>().unwrap(), d);
+        }
+        assert_eq!(Direction::from_char('x'), None);
+        assert!("NE".parse::<Direction>().is_err());
+        assert_eq!("NE".parse::<Direction8>().unwrap(), Direction8::NorthEast);
+        assert_eq!("^".parse::<Direction8>().unwrap(), Direction8::North);
+    }
+
+    #[test]
+    fn test_eight_way() {
+        use Direction8::*;
+        assert_eq!(Direction8::ALL.map(|d| d.as_vector()), Moore::OFFSETS);
+        assert_eq!(North.turn_cw(), NorthEast);
+        assert_eq!(North.rotate_by(Rotation::CW), East);
+        assert_eq!(NorthWest.reverse(), SouthEast);
+        assert_eq!(NorthWest.turns_to(South), 3);
+        assert_eq!(Direction8::from(Direction::West), West);
+        assert_eq!(SouthWest.as_orthogonal(), None);
+        assert_eq!(South.as_orthogonal(), Some(Direction::South));
+        for d in Direction8::ALL {
+            assert_eq!(Direction8::from_vector(d.as_vector()), Some(d));
+            assert_eq!(d.to_string().parse::<Direction8>().unwrap(), d);
+        }
+    }
+}
@@ -1,9 +1,7 @@
-use std::cmp::{max, min};
-use std::collections::{BTreeSet, BinaryHeap};
 use std::fmt;
 
-use super::point::Point;
-use super::DijkstraMetric;
+use super::point::{Point, Reflection, Rotation};
+use super::{Grid, GridParseError, Rect};
 
 type Index = i64;
 
@@ -19,12 +17,10 @@ impl<T: Default> HasEmpty for T {
 
 #[derive(Debug, Clone)]
 pub struct DenseGrid<V: Clone + fmt::Debug> {
-    pub min_x: Index,
-    pub min_y: Index,
-    pub max_x: Index,
-    pub max_y: Index,
+    rect: Rect,
     width: usize,
     height: usize,
+    wrapping: bool,
     cells: Vec<V>,
 }
 
@@ -33,21 +29,15 @@ impl<V: Clone + fmt::Debug + std::hash::Hash> std::hash::Hash for DenseGrid<V> {
     where
         H: std::hash::Hasher,
     {
-        self.min_x.hash(state);
-        self.min_y.hash(state);
-        self.max_x.hash(state);
-        self.max_y.hash(state);
+        self.rect.hash(state);
+        self.wrapping.hash(state);
         self.cells.hash(state);
     }
 }
 
 impl<V: Clone + fmt::Debug + PartialEq> PartialEq for DenseGrid<V> {
     fn eq(&self, other: &Self) -> bool {
-        self.min_x == other.min_x
-            && self.min_y == other.min_y
-            && self.max_x == other.max_x
-            && self.max_y == other.max_y
-            && self.cells == other.cells
+        self.rect == other.rect && self.wrapping == other.wrapping && self.cells == other.cells
     }
 }
 
@@ -58,93 +48,86 @@ impl<V: Clone + fmt::Debug + HasEmpty> DenseGrid<V> {
         Self::new_with(upper_left, lower_right, V::empty_value())
     }
 
+    /// Parse a rectangular block of text, one cell per character. Panics if the input is
+    /// empty or ragged; see `try_from_input` for the checked version.
     pub fn from_input<F>(input: &str, f: F) -> Self
     where
         F: Fn(char) -> V,
     {
-        let height = input.lines().count() as i64 - 1;
-        let width = input.lines().next().unwrap().chars().count() as i64 - 1;
-        let mut g = Self::new_with(
-            Point::new(0, 0),
-            Point::new(width, height),
-            V::empty_value(),
-        );
-        for (y, row) in input.lines().enumerate() {
-            for (x, chr) in row.chars().enumerate() {
-                let coord = Point::new(x as i64, y as i64);
-                let value = f(chr);
-                g.set(coord, value);
-            }
-        }
-        g
+        Self::try_from_input(input, |c| Ok::<_, GridParseError>(f(c)))
+            .unwrap_or_else(|err| panic!("malformed grid input: {}", err))
     }
 
+    /// Parse a rectangular block of text, one cell per character, stopping at the first error
+    /// from `f`. Empty and ragged input are reported as `GridParseError`s.
     pub fn try_from_input<E, F>(input: &str, f: F) -> Result<Self, E>
     where
+        E: From<GridParseError>,
         F: Fn(char) -> Result<V, E>,
     {
-        let height = input.lines().count() as i64 - 1;
-        let width = input.lines().next().unwrap().chars().count() as i64 - 1;
-        let mut g = Self::new_with(
-            Point::new(0, 0),
-            Point::new(width, height),
-            V::empty_value(),
-        );
-        for (y, row) in input.lines().enumerate() {
-            for (x, chr) in row.chars().enumerate() {
-                let coord = Point::new(x as i64, y as i64);
-                let value = f(chr)?;
-                g.set(coord, value);
-            }
-        }
-        Ok(g)
+        let chars = DenseGrid::parse_with(input, &[], Some)?.grid;
+        let cells = chars
+            .iter()
+            .map(|(_, c)| f(*c))
+            .collect::<Result<Vec<_>, E>>()?;
+        Ok(Self::from_cells(chars.origin(), chars.width, cells))
     }
 }
 
 impl<V: Clone + fmt::Debug> DenseGrid<V> {
     pub fn new_with(upper_left: Point<Index>, lower_right: Point<Index>, empty_value: V) -> Self {
-        let min_x = min(upper_left.x, lower_right.x);
-        let max_x = max(upper_left.x, lower_right.x);
-        let min_y = min(upper_left.y, lower_right.y);
-        let max_y = max(upper_left.y, lower_right.y);
-        let width = 1 + max_x.abs_diff(min_x) as usize;
-        let height = 1 + max_y.abs_diff(min_y) as usize;
+        let rect = Rect::new(upper_left, lower_right);
+        let (width, height) = (rect.width(), rect.height());
         Self {
-            min_x,
-            max_x,
-            min_y,
-            max_y,
+            rect,
             width,
             height,
+            wrapping: false,
             cells: vec![empty_value; width * height],
         }
     }
 
+    /// A grid with its upper-left corner at `origin` whose cells are `cells`, row by row.
+    pub(crate) fn from_cells(origin: Point<Index>, width: usize, cells: Vec<V>) -> Self {
+        let height = cells.len() / width;
+        assert_eq!(width * height, cells.len(), "cells must fill whole rows");
+        Self {
+            rect: Rect::with_size(origin, width, height).expect("grids are never empty"),
+            width,
+            height,
+            wrapping: false,
+            cells,
+        }
+    }
+
     pub fn new_with_dimensions_from<T: Clone + fmt::Debug>(
         g: &DenseGrid<T>,
         empty_value: V,
     ) -> Self {
         Self {
-            min_x: g.min_x,
-            max_x: g.max_x,
-            min_y: g.min_y,
-            max_y: g.max_y,
+            rect: g.rect,
             width: g.width,
             height: g.height,
+            wrapping: g.wrapping,
             cells: vec![empty_value; g.width * g.height],
         }
     }
 
     pub fn origin(&self) -> Point {
-        Point::new(self.min_x, self.min_y)
+        self.rect.min
+    }
+
+    /// The cells this grid covers.
+    pub fn rect(&self) -> Rect {
+        self.rect
     }
 
     pub fn row_numbers(&self) -> impl Iterator<Item = Index> {
-        self.min_y..=self.max_y
+        self.rect.min.y..=self.rect.max.y
     }
 
     pub fn column_numbers(&self) -> impl Iterator<Item = Index> {
-        self.min_x..=self.max_x
+        self.rect.min.x..=self.rect.max.x
     }
 
     pub fn width(&self) -> usize {
@@ -159,12 +142,70 @@ impl<V: Clone + fmt::Debug> DenseGrid<V> {
         self.width * self.height
     }
 
+    /// Turn toroidal addressing on or off. When on, coordinates outside the grid are reduced
+    /// modulo its width and height instead of being treated as out-of-bounds.
+    pub fn set_wrapping(&mut self, wrapping: bool) {
+        self.wrapping = wrapping;
+    }
+
+    pub fn with_wrapping(mut self) -> Self {
+        self.wrapping = true;
+        self
+    }
+
+    pub fn is_wrapping(&self) -> bool {
+        self.wrapping
+    }
+
+    /// Reduce a coordinate modulo the grid size, regardless of the addressing mode.
+    pub fn wrap(&self, coordinate: Point<Index>) -> Point<Index> {
+        let origin = self.origin();
+        origin
+            + (coordinate - origin)
+                .rem_euclid(Point::new(self.width as Index, self.height as Index))
+    }
+
     /// Get a value by coordinate. Returns None if the coordinate is out-of-bounds.
     pub fn get(&self, coordinate: Point<Index>) -> Option<V> {
         let index = self.index_for(coordinate)?;
         self.cells.get(index).cloned()
     }
 
+    /// Borrow a value by coordinate, without cloning it. Returns None if the coordinate is
+    /// out-of-bounds.
+    pub fn get_ref(&self, coordinate: Point<Index>) -> Option<&V> {
+        let index = self.index_for(coordinate)?;
+        self.cells.get(index)
+    }
+
+    pub fn get_mut(&mut self, coordinate: Point<Index>) -> Option<&mut V> {
+        let index = self.index_for(coordinate)?;
+        self.cells.get_mut(index)
+    }
+
+    /// The cells of row `y`, left to right. Returns None if the row is out-of-bounds.
+    pub fn row(&self, y: Index) -> Option<&[V]> {
+        if !(self.rect.min.y..=self.rect.max.y).contains(&y) {
+            return None;
+        }
+        let start = (y - self.rect.min.y) as usize * self.width;
+        Some(&self.cells[start..start + self.width])
+    }
+
+    /// The cells of column `x`, top to bottom, borrowed in place. Returns None if the column is
+    /// out-of-bounds.
+    pub fn column(&self, x: Index) -> Option<ColumnView<'_, V>> {
+        if !(self.rect.min.x..=self.rect.max.x).contains(&x) {
+            return None;
+        }
+        let start = (x - self.rect.min.x) as usize;
+        Some(ColumnView {
+            cells: &self.cells[start..],
+            stride: self.width,
+            len: self.height,
+        })
+    }
+
     /// Set a value by coordinate. Returns None if the coordinate is out-of-bounds.
     pub fn set(&mut self, coordinate: Point<Index>, value: V) -> Option<()> {
         let index = self.index_for(coordinate)?;
@@ -172,235 +213,404 @@ impl<V: Clone + fmt::Debug> DenseGrid<V> {
         Some(())
     }
 
+    /// Whether a coordinate lies within the grid's rectangle. This ignores wrapping, so that
+    /// rays and other bounds checks still stop at the edge; use `Grid::normalize` to find the
+    /// cell a coordinate addresses on a wrapping grid.
     pub fn contains(&self, coordinate: Point<Index>) -> bool {
-        coordinate.x >= self.min_x
-            && coordinate.x <= self.max_x
-            && coordinate.y >= self.min_y
-            && coordinate.y <= self.max_y
-    }
-
-    pub fn dump_with<F: Fn(&V) -> char>(&self, f: F) {
-        for y in self.min_y..=self.max_y {
-            let cells = (self.min_x..=self.max_x)
-                .map(|x| {
-                    let coordinate = Point::new(x, y);
-                    f(&self[coordinate])
-                })
-                .collect::<String>();
-            println!("{}", cells);
-        }
-    }
-
-    pub fn save_to_image<F: Fn(&V) -> image::Rgb<u8>, P: AsRef<std::path::Path>>(
-        &self,
-        f: F,
-        path: P,
-    ) -> anyhow::Result<()> {
-        let mut image = image::ImageBuffer::from_pixel(
-            self.width() as u32,
-            self.height() as u32,
-            image::Rgb([255, 255, 255]),
-        );
-        for (point, value) in self.iter() {
-            let point = point - self.origin();
-            image.put_pixel(point.x as u32, point.y as u32, f(&value));
-        }
-        image.save(path.as_ref())?;
-        Ok(())
+        self.rect.contains(coordinate)
     }
 
     fn index_for(&self, coordinate: Point<Index>) -> Option<usize> {
-        if coordinate.x < self.min_x
-            || coordinate.x > self.max_x
-            || coordinate.y < self.min_y
-            || coordinate.y > self.max_y
-        {
+        let coordinate = if self.wrapping {
+            self.wrap(coordinate)
+        } else {
+            coordinate
+        };
+        if !self.rect.contains(coordinate) {
             None
         } else {
-            let row = coordinate.y.abs_diff(self.min_y) as usize * self.width;
-            let col = coordinate.x.abs_diff(self.min_x) as usize;
-            Some(row + col)
+            let offset = coordinate - self.origin();
+            Some(offset.y as usize * self.width + offset.x as usize)
         }
     }
 
-    pub fn rows(&self) -> Rows<V> {
-        Rows {
-            grid: self,
-            y: self.min_y,
+    /// Every cell and its coordinate, row by row, borrowed in place.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            origin: self.origin(),
+            width: self.width,
+            cells: self.cells.iter().enumerate(),
         }
     }
 
-    pub fn columns(&self) -> Columns<V> {
-        Columns {
-            grid: self,
-            x: self.min_x,
+    pub fn iter_mut(&mut self) -> IterMut<'_, V> {
+        IterMut {
+            origin: self.origin(),
+            width: self.width,
+            cells: self.cells.iter_mut().enumerate(),
         }
     }
 
-    pub fn iter(&self) -> Iter<V> {
-        Iter {
+    /// Every `width` by `height` window that fits inside the grid, in row-major order of their
+    /// upper-left corners.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = GridView<'_, V>> {
+        let corners = if width == 0 || height == 0 || width > self.width || height > self.height {
+            None
+        } else {
+            Rect::with_size(
+                self.origin(),
+                self.width - width + 1,
+                self.height - height + 1,
+            )
+        };
+        corners
+            .into_iter()
+            .flat_map(|c| c.points())
+            .map(move |corner| GridView {
+                grid: self,
+                rect: Rect::with_size(corner, width, height).expect("windows are never empty"),
+            })
+    }
+
+    /// A read-only view of the cells inside `rect`, clipped to this grid. Returns None if they
+    /// don't overlap at all.
+    pub fn view(&self, rect: Rect) -> Option<GridView<'_, V>> {
+        Some(GridView {
             grid: self,
-            x: self.min_x,
-            y: self.min_y,
-        }
+            rect: self.rect.intersection(&rect)?,
+        })
     }
 
-    pub fn dijkstra<MV, TF, F>(
-        &self,
-        start: Point,
-        traversible: TF,
-        cost: F,
-    ) -> anyhow::Result<(DenseGrid<DijkstraMetric<MV>>, DenseGrid<Option<Point>>)>
+    /// A grid of the given size with the same origin as this one, where the cell at offset
+    /// (x, y) from the origin is copied from the cell at offset `source(x, y)` in this one.
+    fn remap<F>(&self, width: usize, height: usize, source: F) -> Self
     where
-        MV: std::fmt::Debug
-            + Clone
-            + Copy
-            + PartialEq
-            + Eq
-            + PartialOrd
-            + Ord
-            + num_traits::Zero
-            + std::ops::Add,
-        F: Fn(&Self, Point, Point) -> MV,
-        TF: Fn(&Self, Point) -> bool,
+        F: Fn(usize, usize) -> (usize, usize),
     {
-        use std::cmp::Reverse;
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = source(x, y);
+                cells.push(self.cells[sy * self.width + sx].clone());
+            }
+        }
+        Self {
+            rect: Rect::with_size(self.origin(), width, height).expect("grids are never empty"),
+            width,
+            height,
+            wrapping: self.wrapping,
+            cells,
+        }
+    }
 
-        if !self.contains(start) {
-            anyhow::bail!("start point not contained in map");
+    /// Rotate the contents, in the same sense as `Point::rotate_by`. The result keeps this
+    /// grid's origin; quarter turns swap the width and height.
+    pub fn rotate(&self, r: Rotation) -> Self {
+        let (w, h) = (self.width, self.height);
+        match r {
+            Rotation::CW => self.remap(h, w, |x, y| (y, h - 1 - x)),
+            Rotation::CCW => self.remap(h, w, |x, y| (w - 1 - y, x)),
+            Rotation::Mirror => self.remap(w, h, |x, y| (w - 1 - x, h - 1 - y)),
         }
-        let mut new = DenseGrid::new_with_dimensions_from(&self, DijkstraMetric::Infinite);
-        new.set(start, DijkstraMetric::Finite(MV::zero()));
-        let mut preds = DenseGrid::new_with_dimensions_from(&self, None);
-        let mut unvisited = BinaryHeap::new();
-        let mut visited = BTreeSet::new();
-        unvisited.push((Reverse(DijkstraMetric::Finite(MV::zero())), start));
-        while let Some((Reverse(DijkstraMetric::Finite(current)), point)) = unvisited.pop() {
-            if visited.contains(&point) {
+    }
+
+    /// Swap rows and columns. The result keeps this grid's origin.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Mirror left-to-right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width;
+        self.remap(w, self.height, |x, y| (w - 1 - x, y))
+    }
+
+    /// Mirror top-to-bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height;
+        self.remap(self.width, h, |x, y| (x, h - 1 - y))
+    }
+
+    /// Mirror across a line through the middle of the grid, in the same sense as
+    /// `Point::reflect`.
+    pub fn reflect(&self, r: Reflection) -> Self {
+        match r {
+            Reflection::Horizontal => self.flip_horizontal(),
+            Reflection::Vertical => self.flip_vertical(),
+            Reflection::Diagonal => self.transpose(),
+            Reflection::AntiDiagonal => self.rotate(Rotation::CW).flip_vertical(),
+        }
+    }
+
+    /// All eight rotations and reflections of this grid, starting with the grid itself. They
+    /// aren't necessarily distinct if the grid has symmetries.
+    pub fn orientations(&self) -> Vec<Self> {
+        let turns = [Rotation::CW, Rotation::Mirror, Rotation::CCW];
+        let transposed = self.transpose();
+        let mut res = vec![self.clone()];
+        res.extend(turns.map(|r| self.rotate(r)));
+        res.extend(turns.map(|r| transposed.rotate(r)));
+        res.push(transposed);
+        res
+    }
+
+    /// A copy of the cells inside `rect`, clipped to this grid and keeping their coordinates.
+    /// Returns None if they don't overlap at all.
+    pub fn subgrid(&self, rect: Rect) -> Option<Self> {
+        Some(self.view(rect)?.to_grid())
+    }
+
+    /// Copy every cell of `other` into this grid, shifted so that `other`'s origin lands on
+    /// `at`. Cells that land outside this grid are dropped, even if it's wrapping.
+    pub fn paste(&mut self, other: &DenseGrid<V>, at: Point<Index>) {
+        let shift = at - other.origin();
+        for (point, value) in other.iter() {
+            let target = point + shift;
+            if !self.contains(target) {
                 continue;
             }
-            for neighbor in point.ordinal_neighbors_array() {
-                if !self.contains(neighbor) {
-                    continue;
-                }
-                if visited.contains(&neighbor) {
-                    continue;
-                }
-                if !traversible(&self, neighbor) {
-                    continue;
-                }
-                let distance = cost(&self, point, neighbor);
-                let next = current + distance;
-                let val = match new.get(neighbor) {
-                    Some(DijkstraMetric::Finite(v)) => {
-                        if next < v {
-                            new.set(neighbor, DijkstraMetric::Finite(next));
-                            preds.set(neighbor, Some(point));
-                            next
-                        } else {
-                            v
-                        }
-                    }
-                    Some(DijkstraMetric::Infinite) => {
-                        new.set(neighbor, DijkstraMetric::Finite(next));
-                        preds.set(neighbor, Some(point));
-                        next
-                    }
-                    None => {
-                        continue;
-                    }
-                };
-                unvisited.push((Reverse(DijkstraMetric::Finite(val)), neighbor));
+            if let Some(cell) = self.get_mut(target) {
+                *cell = value.clone();
             }
-            visited.insert(point);
         }
-        Ok((new, preds))
     }
 }
 
-impl<V: Clone + PartialEq + fmt::Debug> DenseGrid<V> {
-    pub fn find(&self, value: &V) -> Option<Point> {
-        self.iter()
-            .find_map(|(p, v)| if v == *value { Some(p) } else { None })
+impl<V: Clone + fmt::Debug + PartialEq> DenseGrid<V> {
+    /// Every place `pattern` appears in this grid, as the coordinate its origin lands on. Cells
+    /// of the pattern equal to `wildcard` match anything.
+    pub fn find_pattern(&self, pattern: &DenseGrid<V>, wildcard: Option<&V>) -> Vec<Point> {
+        let cells = pattern
+            .iter()
+            .filter(|(_, v)| Some(*v) != wildcard)
+            .map(|(p, v)| (p - pattern.origin(), v))
+            .collect::<Vec<_>>();
+        self.windows(pattern.width, pattern.height)
+            .map(|w| w.rect().min)
+            .filter(|corner| {
+                cells
+                    .iter()
+                    .all(|(offset, v)| self.get_ref(*corner + *offset) == Some(*v))
+            })
+            .collect()
+    }
+
+    /// Like `find_pattern`, but under each of the pattern's `orientations`. Returns every match
+    /// along with the index of the orientation that matched; orientations identical to an
+    /// earlier one are skipped, so symmetric patterns aren't counted more than once.
+    pub fn find_pattern_oriented(
+        &self,
+        pattern: &DenseGrid<V>,
+        wildcard: Option<&V>,
+    ) -> Vec<(Point, usize)> {
+        let orientations = pattern.orientations();
+        let mut res = vec![];
+        for (i, oriented) in orientations.iter().enumerate() {
+            if orientations[..i].contains(oriented) {
+                continue;
+            }
+            res.extend(
+                self.find_pattern(oriented, wildcard)
+                    .into_iter()
+                    .map(|p| (p, i)),
+            );
+        }
+        res
     }
 }
 
-pub struct Rows<'a, V: Clone + std::fmt::Debug> {
-    grid: &'a DenseGrid<V>,
-    y: Index,
+pub struct Iter<'a, V: Clone + std::fmt::Debug> {
+    origin: Point<Index>,
+    width: usize,
+    cells: std::iter::Enumerate<std::slice::Iter<'a, V>>,
 }
 
-impl<'a, V: Clone + std::fmt::Debug> Iterator for Rows<'a, V> {
-    type Item = Vec<V>;
+impl<'a, V: Clone + std::fmt::Debug> Iterator for Iter<'a, V> {
+    type Item = (Point<Index>, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.y > self.grid.max_y {
-            return None;
-        }
-        let val = self
-            .grid
-            .column_numbers()
-            .map(|x| {
-                let pt = Point::new(x, self.y);
-                self.grid.get(pt).unwrap()
-            })
-            .collect();
-        self.y += 1;
-        Some(val)
+        let (i, value) = self.cells.next()?;
+        Some((offset_point(self.origin, self.width, i), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
     }
 }
 
-pub struct Columns<'a, V: Clone + std::fmt::Debug> {
-    grid: &'a DenseGrid<V>,
-    x: Index,
+impl<V: Clone + std::fmt::Debug> ExactSizeIterator for Iter<'_, V> {}
+
+impl<V: Clone + std::fmt::Debug> std::iter::FusedIterator for Iter<'_, V> {}
+
+pub struct IterMut<'a, V: Clone + std::fmt::Debug> {
+    origin: Point<Index>,
+    width: usize,
+    cells: std::iter::Enumerate<std::slice::IterMut<'a, V>>,
 }
 
-impl<'a, V: Clone + std::fmt::Debug> Iterator for Columns<'a, V> {
-    type Item = Vec<V>;
+impl<'a, V: Clone + std::fmt::Debug> Iterator for IterMut<'a, V> {
+    type Item = (Point<Index>, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.x > self.grid.max_x {
-            return None;
+        let (i, value) = self.cells.next()?;
+        Some((offset_point(self.origin, self.width, i), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
+
+impl<V: Clone + std::fmt::Debug> ExactSizeIterator for IterMut<'_, V> {}
+
+impl<V: Clone + std::fmt::Debug> std::iter::FusedIterator for IterMut<'_, V> {}
+
+/// The coordinate of the `index`th cell of a grid stored row by row.
+fn offset_point(origin: Point<Index>, width: usize, index: usize) -> Point<Index> {
+    origin + Point::new((index % width) as Index, (index / width) as Index)
+}
+
+/// One column of a `DenseGrid`, from `DenseGrid::column`.
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnView<'a, V> {
+    cells: &'a [V],
+    stride: usize,
+    len: usize,
+}
+
+impl<'a, V> ColumnView<'a, V> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The `index`th cell from the top.
+    pub fn get(&self, index: usize) -> Option<&'a V> {
+        if index < self.len {
+            self.cells.get(index * self.stride)
+        } else {
+            None
         }
-        let val = self
-            .grid
-            .row_numbers()
-            .map(|y| {
-                let pt = Point::new(self.x, y);
-                self.grid.get(pt).unwrap()
-            })
-            .collect();
-        self.x += 1;
-        Some(val)
+    }
+
+    pub fn iter(&self) -> std::iter::StepBy<std::slice::Iter<'a, V>> {
+        self.cells[..(self.len - 1) * self.stride + 1]
+            .iter()
+            .step_by(self.stride)
     }
 }
 
-pub struct Iter<'a, V: Clone + std::fmt::Debug> {
-    grid: &'a DenseGrid<V>,
-    x: Index,
-    y: Index,
+impl<'a, V> IntoIterator for ColumnView<'a, V> {
+    type Item = &'a V;
+    type IntoIter = std::iter::StepBy<std::slice::Iter<'a, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
-impl<'a, V: Clone + std::fmt::Debug> Iterator for Iter<'a, V> {
-    type Item = (Point<Index>, V);
+impl<V: Clone + fmt::Debug> Grid for DenseGrid<V> {
+    type Value = V;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.y > self.grid.max_y {
-            return None;
+    fn bounds(&self) -> Option<(Point<Index>, Point<Index>)> {
+        Some(self.rect.into())
+    }
+
+    fn get(&self, coordinate: Point<Index>) -> Option<V> {
+        DenseGrid::get(self, coordinate)
+    }
+
+    fn set(&mut self, coordinate: Point<Index>, value: V) -> Option<()> {
+        DenseGrid::set(self, coordinate, value)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (Point<Index>, V)> + '_ {
+        DenseGrid::iter(self).map(|(p, v)| (p, v.clone()))
+    }
+
+    fn contains(&self, coordinate: Point<Index>) -> bool {
+        DenseGrid::contains(self, coordinate)
+    }
+
+    fn is_wrapping(&self) -> bool {
+        self.wrapping
+    }
+
+    fn normalize(&self, coordinate: Point<Index>) -> Option<Point<Index>> {
+        if self.wrapping {
+            Some(self.wrap(coordinate))
+        } else if self.contains(coordinate) {
+            Some(coordinate)
+        } else {
+            None
         }
-        let pt = Point::new(self.x, self.y);
-        let value = self.grid.get(pt).unwrap();
-        if self.x >= self.grid.max_x {
-            self.x = self.grid.min_x;
-            self.y += 1;
+    }
+}
+
+/// A rectangular window onto part of a `DenseGrid`, from `DenseGrid::view`. Cells outside the
+/// window are out-of-bounds, so every `Grid` algorithm can be run on just that part. Views are
+/// read-only; `set` always returns None.
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, V: Clone + fmt::Debug> {
+    grid: &'a DenseGrid<V>,
+    rect: Rect,
+}
+
+impl<'a, V: Clone + fmt::Debug> GridView<'a, V> {
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn get_ref(&self, coordinate: Point<Index>) -> Option<&'a V> {
+        if self.rect.contains(coordinate) {
+            self.grid.get_ref(coordinate)
         } else {
-            self.x += 1;
+            None
+        }
+    }
+
+    /// Copy the viewed cells into a grid of their own, keeping their coordinates.
+    pub fn to_grid(&self) -> DenseGrid<V> {
+        DenseGrid {
+            rect: self.rect,
+            width: self.rect.width(),
+            height: self.rect.height(),
+            wrapping: false,
+            cells: self.rect.points().map(|p| self.grid[p].clone()).collect(),
         }
-        Some((pt, value))
     }
 }
 
-impl<'a, V: Clone + std::fmt::Debug> std::iter::FusedIterator for Iter<'a, V> {}
+impl<V: Clone + fmt::Debug> Grid for GridView<'_, V> {
+    type Value = V;
+
+    fn bounds(&self) -> Option<(Point<Index>, Point<Index>)> {
+        Some(self.rect.into())
+    }
+
+    fn get(&self, coordinate: Point<Index>) -> Option<V> {
+        if self.rect.contains(coordinate) {
+            self.grid.get(coordinate)
+        } else {
+            None
+        }
+    }
+
+    fn set(&mut self, _coordinate: Point<Index>, _value: V) -> Option<()> {
+        None
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (Point<Index>, V)> + '_ {
+        self.rect.points().map(|p| (p, self.grid[p].clone()))
+    }
+
+    fn contains(&self, coordinate: Point<Index>) -> bool {
+        self.rect.contains(coordinate)
+    }
+}
 
 impl<V: Clone + std::fmt::Debug> std::ops::Index<Point<Index>> for DenseGrid<V> {
     type Output = V;
@@ -421,6 +631,7 @@ impl<V: Clone + std::fmt::Debug> std::ops::IndexMut<Point<Index>> for DenseGrid<
 #[cfg(test)]
 mod tests {
     use super::{DenseGrid, Point};
+    use crate::{Grid, GridParseError, Rect};
 
     #[test]
     fn test_small() {
@@ -443,6 +654,26 @@ mod tests {
         assert_eq!(g[Point { x: 50, y: 50 }], 4);
     }
 
+    #[test]
+    fn test_from_input() {
+        let g = DenseGrid::from_input("12\r\n34\n\n", |c| c.to_digit(10).unwrap());
+        assert_eq!((g.width(), g.height()), (2, 2));
+        assert_eq!(g[Point::new(1, 1)], 4);
+
+        let digits = |c: char| c.to_digit(10).ok_or(anyhow::anyhow!("bad digit {:?}", c));
+        assert!(DenseGrid::try_from_input("", digits).is_err());
+        let ragged = DenseGrid::try_from_input("12\n3", digits).unwrap_err();
+        assert_eq!(
+            ragged.downcast_ref::<GridParseError>(),
+            Some(&GridParseError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert!(DenseGrid::try_from_input("12\n3x", digits).is_err());
+    }
+
     #[test]
     fn test_columns() {
         let mut g = DenseGrid::new_with(Point { x: 0, y: 0 }, Point { x: 3, y: 3 }, 0u8);
@@ -483,7 +714,8 @@ mod tests {
                 |g, p| g.get(p) == Some(true),
                 |_, _, _| 1usize,
             )
-            .expect("should evaluate");
+            .expect("should evaluate")
+            .into_parts();
         assert_eq!(
             res.get(Point::new(0, 0)).unwrap(),
             DijkstraMetric::Finite(0)
@@ -496,4 +728,265 @@ mod tests {
         );
         assert_eq!(preds.get(Point::new(3, 3)).unwrap(), Some(Point::new(2, 3)));
     }
+
+    #[test]
+    fn test_neighborhoods() {
+        use crate::{Moore, VonNeumann};
+        let g = DenseGrid::from_input("#..\n.#.\n..#", |c| c == '.');
+        let res = g
+            .dijkstra_with(Point::new(0, 0), &Moore, |_, _| true, |_, _, _| 1usize)
+            .expect("should evaluate");
+        assert_eq!(res.distance_to(Point::new(2, 2)), Some(2));
+
+        let open = |g: &DenseGrid<bool>, _, p| g.get(p) == Some(true);
+        assert_eq!(g.flood_fill(Point::new(1, 0), &VonNeumann, open).len(), 3);
+        assert_eq!(g.flood_fill(Point::new(1, 0), &Moore, open).len(), 6);
+    }
+
+    #[test]
+    fn test_astar() {
+        let g = DenseGrid::from_input(".#...\n.#.#.\n...#.", |c| c == '.');
+        let (cost, path) = g
+            .astar_to(
+                Point::new(0, 0),
+                Point::new(4, 0),
+                |g, p| g.get(p) == Some(true),
+                |_, _, _| 1usize,
+            )
+            .expect("should find a path");
+        assert_eq!(cost, 8);
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.first(), Some(&Point::new(0, 0)));
+        assert_eq!(path.last(), Some(&Point::new(4, 0)));
+        assert!(path
+            .iter()
+            .zip(path.iter().skip(1))
+            .all(|(a, b)| a.manhattan_distance_to(*b) == 1));
+
+        let res = g.astar(
+            Point::new(0, 0),
+            |_, p| p.x == 2,
+            |g, p| g.get(p) == Some(true),
+            |_, _, _| 1usize,
+            |_, p| 2usize.saturating_sub(p.x as usize),
+        );
+        assert_eq!(res.map(|(c, _)| c), Some(4));
+
+        assert!(g
+            .astar_to(
+                Point::new(0, 0),
+                Point::new(4, 2),
+                |g, p| g.get(p) == Some(true) && p != Point::new(4, 1),
+                |_, _, _| 1usize,
+            )
+            .is_none());
+
+        let ring = DenseGrid::new_with(Point::new(0, 0), Point::new(6, 0), true).with_wrapping();
+        let (cost, path) = ring
+            .astar_to(
+                Point::new(0, 0),
+                Point::new(4, 0),
+                |_, _| true,
+                |_, _, _| 1usize,
+            )
+            .expect("should find a path");
+        assert_eq!(cost, 3);
+        assert_eq!(path[1], Point::new(6, 0));
+
+        let wide = DenseGrid::new_with(Point::new(0, 0), Point::new(299, 0), true);
+        let res = wide.astar_to(
+            Point::new(0, 0),
+            Point::new(299, 0),
+            |_, _| true,
+            |_, _, _| 0u8,
+        );
+        assert_eq!(res.map(|(c, p)| (c, p.len())), Some((0, 300)));
+    }
+
+    #[test]
+    fn test_transforms() {
+        use crate::{Reflection, Rotation};
+
+        let g = DenseGrid::from_input("abc\ndef", |c| c);
+        let as_string = |g: &DenseGrid<char>| {
+            g.row_numbers()
+                .map(|y| {
+                    g.column_numbers()
+                        .map(|x| g[Point::new(x, y)])
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert_eq!(as_string(&g.rotate(Rotation::CW)), "da\neb\nfc");
+        assert_eq!(as_string(&g.rotate(Rotation::CCW)), "cf\nbe\nad");
+        assert_eq!(as_string(&g.rotate(Rotation::Mirror)), "fed\ncba");
+        assert_eq!(as_string(&g.transpose()), "ad\nbe\ncf");
+        assert_eq!(as_string(&g.flip_horizontal()), "cba\nfed");
+        assert_eq!(as_string(&g.flip_vertical()), "def\nabc");
+        assert_eq!(
+            as_string(&g.reflect(Reflection::AntiDiagonal)),
+            "fc\neb\nda"
+        );
+        assert_eq!(g.rotate(Rotation::CW).width(), 2);
+
+        let orientations = g.orientations();
+        assert_eq!(orientations[0], g);
+        assert_eq!(orientations.len(), 8);
+        for (i, a) in orientations.iter().enumerate() {
+            for b in orientations.iter().skip(i + 1) {
+                assert_ne!(a, b);
+            }
+        }
+
+        // moving a cell along with the grid agrees with rotating its coordinate
+        let rotated = g.rotate(Rotation::CW);
+        let p = Point::new(2, 0);
+        let moved = p.rotate_by(Rotation::CW) + Point::new(g.height() as i64 - 1, 0);
+        assert_eq!(rotated[moved], g[p]);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let mut g = DenseGrid::new_with(Point::new(0, 0), Point::new(2, 1), 0u8).with_wrapping();
+        g.set(Point::new(-1, 0), 1);
+        assert_eq!(g.get(Point::new(2, 0)), Some(1));
+        assert_eq!(g.get(Point::new(5, 2)), Some(1));
+        g[Point::new(3, -1)] = 2;
+        assert_eq!(g[Point::new(0, 1)], 2);
+        assert!(!g.contains(Point::new(3, 0)));
+        assert_eq!(g.normalize(Point::new(3, 0)), Some(Point::new(0, 0)));
+        assert_eq!(
+            g.neighbors(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1), Point::new(2, 0)]
+        );
+        let thin = DenseGrid::new_with(Point::new(0, 0), Point::new(0, 1), 0u8).with_wrapping();
+        assert_eq!(
+            thin.neighbors(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point::new(0, 1)]
+        );
+
+        let res = g
+            .dijkstra(Point::new(0, 0), |_, _| true, |_, _, _| 1usize)
+            .expect("should evaluate");
+        assert_eq!(res.distance_to(Point::new(2, 0)), Some(1));
+        assert_eq!(res.distance_to(Point::new(2, 1)), Some(2));
+    }
+
+    #[test]
+    fn test_view() {
+        let g = DenseGrid::from_input("#....\n.#.#.\n...#.", |c| c == '.');
+        assert_eq!(g.rect(), Rect::new(Point::new(0, 0), Point::new(4, 2)));
+        let view = g
+            .view(Rect::new(Point::new(2, 1), Point::new(9, 9)))
+            .expect("overlaps");
+        assert_eq!(view.rect(), Rect::new(Point::new(2, 1), Point::new(4, 2)));
+        assert_eq!(view.get(Point::new(0, 0)), None);
+        assert_eq!(view.get(Point::new(2, 1)), Some(true));
+        assert_eq!(view.iter().filter(|(_, open)| *open).count(), 4);
+        // (2, 1) and (4, 1) only connect through cells outside the view
+        let whole = g.flood_fill(Point::new(2, 1), &crate::VonNeumann, |g, _, p| {
+            g.get(p) == Some(true)
+        });
+        assert_eq!(whole.len(), 11);
+        let part = view.flood_fill(Point::new(2, 1), &crate::VonNeumann, |v, _, p| {
+            v.get(p) == Some(true)
+        });
+        assert_eq!(part.len(), 2);
+        let copy = view.to_grid();
+        assert_eq!(copy.origin(), Point::new(2, 1));
+        assert_eq!(copy.size(), 6);
+        assert!(g
+            .view(Rect::new(Point::new(5, 0), Point::new(6, 1)))
+            .is_none());
+    }
+
+    #[test]
+    fn test_borrowing() {
+        let mut g = DenseGrid::from_input("abc\ndef\nghi", |c| c.to_string());
+        assert_eq!(g.get_ref(Point::new(1, 1)).map(String::as_str), Some("e"));
+        assert_eq!(g.get_ref(Point::new(3, 1)), None);
+        assert_eq!(g.row(1).unwrap().concat(), "def");
+        assert_eq!(g.row(3), None);
+        let column = g.column(2).unwrap();
+        assert_eq!(column.len(), 3);
+        assert_eq!(column.get(1).map(String::as_str), Some("f"));
+        assert_eq!(column.iter().rev().cloned().collect::<String>(), "ifc");
+        assert!(g.column(-1).is_none());
+
+        let mut iter = g.iter();
+        assert_eq!(iter.len(), 9);
+        let (p, v) = iter.nth(5).unwrap();
+        assert_eq!((p, v.as_str()), (Point::new(2, 1), "f"));
+
+        for (p, v) in g.iter_mut() {
+            if p.x == p.y {
+                v.make_ascii_uppercase();
+            }
+        }
+        g.get_mut(Point::new(0, 2)).unwrap().push('!');
+        assert_eq!(
+            g.column(0)
+                .unwrap()
+                .into_iter()
+                .cloned()
+                .collect::<String>(),
+            "Adg!"
+        );
+
+        let windows = g.windows(2, 3).collect::<Vec<_>>();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(
+            windows[1].rect(),
+            Rect::new(Point::new(1, 0), Point::new(2, 2))
+        );
+        assert_eq!(
+            windows[1].get_ref(Point::new(1, 1)).map(String::as_str),
+            Some("E")
+        );
+        assert_eq!(windows[1].get_ref(Point::new(0, 1)), None);
+        assert_eq!(g.windows(1, 1).count(), 9);
+        assert_eq!(g.windows(4, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_patterns() {
+        use std::collections::BTreeSet;
+
+        let mut g = DenseGrid::from_input("abcd\nefgh\nijkl", |c| c);
+        let middle = g
+            .subgrid(Rect::new(Point::new(1, 1), Point::new(5, 2)))
+            .unwrap();
+        assert_eq!(middle.origin(), Point::new(1, 1));
+        assert_eq!((middle.width(), middle.height()), (3, 2));
+        assert_eq!(middle[Point::new(3, 2)], 'l');
+        assert!(g
+            .subgrid(Rect::new(Point::new(4, 0), Point::new(4, 0)))
+            .is_none());
+
+        g.paste(&middle, Point::new(-1, 0));
+        assert_eq!(g.row(0).unwrap().iter().collect::<String>(), "ghcd");
+        assert_eq!(g.row(1).unwrap().iter().collect::<String>(), "klgh");
+        let mut ring = DenseGrid::from_input("abcd", |c| c).with_wrapping();
+        ring.paste(&DenseGrid::from_input("xy", |c| c), Point::new(3, 0));
+        assert_eq!(ring.row(0).unwrap().iter().collect::<String>(), "abcx");
+
+        let g = DenseGrid::from_input("XMAS\nM..A\nA..M\nSAMX", |c| c);
+        let across = DenseGrid::from_input("XMAS", |c| c);
+        assert_eq!(g.find_pattern(&across, None), vec![Point::new(0, 0)]);
+        let found = g.find_pattern_oriented(&across, None);
+        // along the top, down the left, backwards along the bottom and up the right
+        assert_eq!(found.len(), 4);
+        let distinct = found.iter().map(|(_, o)| *o).collect::<BTreeSet<_>>();
+        assert_eq!(distinct.len(), 4);
+
+        let g = DenseGrid::from_input("XX...\n.MM..\n..AA.\n...SS", |c| c);
+        let diagonal = DenseGrid::from_input("X...\n.M..\n..A.\n...S", |c| c);
+        assert!(g.find_pattern(&diagonal, None).is_empty());
+        assert_eq!(
+            g.find_pattern(&diagonal, Some(&'.')),
+            vec![Point::new(0, 0), Point::new(1, 0)]
+        );
+        assert_eq!(g.find_pattern_oriented(&diagonal, Some(&'.')).len(), 2);
+    }
 }
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::{Directed, EdgeType};
+
+use super::point::Point;
+
+/// A petgraph `Graph` built from a grid, with one node per cell (weighted by its coordinate)
+/// and an index for getting from a cell back to its node.
+#[derive(Debug, Clone)]
+pub struct GridGraph<E, Ty: EdgeType = Directed> {
+    pub graph: Graph<Point, E, Ty>,
+    pub nodes: BTreeMap<Point, NodeIndex>,
+}
+
+impl<E, Ty: EdgeType> GridGraph<E, Ty> {
+    pub fn new(graph: Graph<Point, E, Ty>, nodes: BTreeMap<Point, NodeIndex>) -> Self {
+        Self { graph, nodes }
+    }
+
+    pub fn node_for(&self, point: Point) -> Option<NodeIndex> {
+        self.nodes.get(&point).copied()
+    }
+
+    pub fn point_for(&self, node: NodeIndex) -> Point {
+        self.graph[node]
+    }
+
+    pub fn into_parts(self) -> (Graph<Point, E, Ty>, BTreeMap<Point, NodeIndex>) {
+        (self.graph, self.nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::{Directed, Undirected};
+
+    use super::GridGraph;
+    use crate::{DenseGrid, Grid, Moore, Point, SparseGrid};
+
+    #[test]
+    fn test_to_graph() {
+        let g = DenseGrid::from_input("012\n121\n234", |c| c.to_digit(10).unwrap());
+        let climbs = g.to_graph(|g, a, b| g.get(b) == g.get(a).map(|v| v + 1));
+        assert_eq!(climbs.graph.node_count(), 9);
+        let origin = climbs.node_for(Point::new(0, 0)).unwrap();
+        assert_eq!(climbs.point_for(origin), Point::new(0, 0));
+        assert_eq!(climbs.graph.neighbors(origin).count(), 2);
+        let top = climbs.node_for(Point::new(2, 2)).unwrap();
+        assert_eq!(climbs.graph.neighbors(top).count(), 0);
+
+        let same = g.to_ungraph(|g, a, b| g.get(a) == g.get(b));
+        // the two 1s and two 2s along the anti-diagonal aren't orthogonally adjacent
+        assert_eq!(same.graph.edge_count(), 0);
+
+        let weighted = g.to_weighted_graph(|g, a, b| {
+            let (a, b) = (g.get(a)?, g.get(b)?);
+            (b > a).then(|| b - a)
+        });
+        let costs = petgraph::algo::dijkstra(
+            &weighted.graph,
+            origin,
+            weighted.node_for(Point::new(2, 2)),
+            |e| *e.weight(),
+        );
+        assert_eq!(costs[&weighted.node_for(Point::new(2, 2)).unwrap()], 4);
+
+        let diagonal: GridGraph<(), Undirected> =
+            g.to_graph_in(&Moore, |g, a, b| (g.get(a) == g.get(b)).then_some(()));
+        assert_eq!(diagonal.graph.edge_count(), 4);
+        let directed: GridGraph<(), Directed> =
+            g.to_graph_in(&Moore, |g, a, b| (g.get(a) == g.get(b)).then_some(()));
+        assert_eq!(directed.graph.edge_count(), 8);
+    }
+
+    #[test]
+    fn test_sparse_and_wrapping() {
+        let mut sparse = SparseGrid::<u8>::new();
+        sparse.set(Point::new(0, 0), 1);
+        sparse.set(Point::new(2, 0), 1);
+        let all = sparse.to_graph(|_, _, _| true);
+        assert_eq!(all.graph.node_count(), 3);
+        assert_eq!(all.graph.edge_count(), 4);
+
+        let ring = DenseGrid::new_with(Point::new(0, 0), Point::new(1, 0), 0u8).with_wrapping();
+        assert_eq!(ring.to_ungraph(|_, _, _| true).graph.edge_count(), 1);
+        assert_eq!(ring.to_graph(|_, _, _| true).graph.edge_count(), 2);
+    }
+}
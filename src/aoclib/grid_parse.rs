@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::point::Point;
+use super::DenseGrid;
+
+/// Why a block of text couldn't be parsed into a grid. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    Empty,
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    UnexpectedChar {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "grid input is empty"),
+            GridParseError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} is {} characters wide, expected {}",
+                line, found, expected
+            ),
+            GridParseError::UnexpectedChar {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "line {}, column {}: unexpected character {:?}",
+                line, column, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+/// A grid parsed by `DenseGrid::parse_with`, along with where each requested marker character
+/// was found.
+#[derive(Debug, Clone)]
+pub struct ParsedGrid<V: Clone + fmt::Debug> {
+    pub grid: DenseGrid<V>,
+    pub markers: HashMap<char, Vec<Point>>,
+}
+
+impl<V: Clone + fmt::Debug> ParsedGrid<V> {
+    /// Every position of `marker`, in reading order.
+    pub fn all(&self, marker: char) -> &[Point] {
+        self.markers.get(&marker).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// The position of a marker that should appear exactly once.
+    pub fn marker(&self, marker: char) -> anyhow::Result<Point> {
+        match self.all(marker) {
+            [point] => Ok(*point),
+            [] => anyhow::bail!("no {:?} in the grid", marker),
+            many => anyhow::bail!("{} {:?}s in the grid, expected one", many.len(), marker),
+        }
+    }
+}
+
+impl<V: Clone + fmt::Debug> DenseGrid<V> {
+    /// Parse a rectangular block of text, one cell per character, with the upper-left
+    /// character at (0, 0). `f` maps each character to a cell, returning None for characters
+    /// that aren't allowed. CRLF line endings and trailing blank lines are accepted.
+    ///
+    /// The positions of any characters in `markers` are collected as well, so start and end
+    /// points don't need to be searched for afterwards; they're still passed through `f`.
+    pub fn parse_with<F>(
+        input: &str,
+        markers: &[char],
+        f: F,
+    ) -> Result<ParsedGrid<V>, GridParseError>
+    where
+        F: Fn(char) -> Option<V>,
+    {
+        let mut lines = input
+            .lines()
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect::<Vec<_>>();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let Some(width) = lines.first().map(|line| line.chars().count()) else {
+            return Err(GridParseError::Empty);
+        };
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        let mut found = HashMap::new();
+        for (y, line) in lines.iter().enumerate() {
+            let mut line_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                let value = f(c).ok_or(GridParseError::UnexpectedChar {
+                    line: y + 1,
+                    column: x + 1,
+                    found: c,
+                })?;
+                if markers.contains(&c) {
+                    found
+                        .entry(c)
+                        .or_insert_with(Vec::new)
+                        .push(Point::new(x as i64, y as i64));
+                }
+                cells.push(value);
+                line_width += 1;
+            }
+            if line_width != width {
+                return Err(GridParseError::Ragged {
+                    line: y + 1,
+                    expected: width,
+                    found: line_width,
+                });
+            }
+        }
+        Ok(ParsedGrid {
+            grid: DenseGrid::from_cells(Point::new(0, 0), width, cells),
+            markers: found,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GridParseError;
+    use crate::{DenseGrid, Point};
+
+    fn cell(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(false),
+            '.' | 'S' | 'E' => Some(true),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = DenseGrid::parse_with("#S.\r\n.E.\r\n..S\r\n\r\n", &['S', 'E'], cell).unwrap();
+        assert_eq!((parsed.grid.width(), parsed.grid.height()), (3, 3));
+        assert!(!parsed.grid[Point::new(0, 0)]);
+        assert!(parsed.grid[Point::new(1, 0)]);
+        assert_eq!(parsed.marker('E').unwrap(), Point::new(1, 1));
+        assert_eq!(parsed.all('S'), &[Point::new(1, 0), Point::new(2, 2)]);
+        assert!(parsed.marker('S').is_err());
+        assert!(parsed.marker('@').is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            DenseGrid::parse_with("", &[], cell).unwrap_err(),
+            GridParseError::Empty
+        );
+        assert_eq!(
+            DenseGrid::parse_with("\n\n", &[], cell).unwrap_err(),
+            GridParseError::Empty
+        );
+        assert_eq!(
+            DenseGrid::parse_with("...\n..\n...", &[], cell).unwrap_err(),
+            GridParseError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            }
+        );
+        let err = DenseGrid::parse_with("...\n..x", &[], cell).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::UnexpectedChar {
+                line: 2,
+                column: 3,
+                found: 'x'
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: unexpected character 'x'"
+        );
+    }
+}
@@ -0,0 +1,754 @@
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use petgraph::graph::Graph;
+use petgraph::{EdgeType, Undirected};
+
+use super::point::Point;
+use super::{
+    AllShortestPaths, BlockerIndex, CorridorOptions, DenseGrid, DijkstraMetric, GridGraph,
+    JunctionGraph, Neighborhood, Rect, Region, ShortestPaths, StateSearch, VonNeumann,
+};
+
+type Index = i64;
+
+/// Common interface over grid storage.
+///
+/// Implementors only need to provide bounds, cell access and iteration; searching, rendering
+/// and image export are written once here in terms of those.
+pub trait Grid {
+    type Value: Clone + fmt::Debug;
+
+    /// The upper-left and lower-right corners (inclusive), or None if the grid has no cells.
+    fn bounds(&self) -> Option<(Point<Index>, Point<Index>)>;
+
+    /// Get a value by coordinate. Returns None if the coordinate is out-of-bounds.
+    fn get(&self, coordinate: Point<Index>) -> Option<Self::Value>;
+
+    /// Set a value by coordinate. Returns None if the coordinate could not be stored.
+    fn set(&mut self, coordinate: Point<Index>, value: Self::Value) -> Option<()>;
+
+    /// Every cell in the bounding box and its value, row by row. On a `SparseGrid` that
+    /// includes cells that were never written, which read as the empty value.
+    fn iter(&self) -> impl Iterator<Item = (Point<Index>, Self::Value)> + '_;
+
+    fn contains(&self, coordinate: Point<Index>) -> bool {
+        match self.bounds() {
+            Some((ul, lr)) => {
+                coordinate.x >= ul.x
+                    && coordinate.x <= lr.x
+                    && coordinate.y >= ul.y
+                    && coordinate.y <= lr.y
+            }
+            None => false,
+        }
+    }
+
+    /// Map a coordinate to the cell it addresses, or None if it's out-of-bounds.
+    fn normalize(&self, coordinate: Point<Index>) -> Option<Point<Index>> {
+        if self.contains(coordinate) {
+            Some(coordinate)
+        } else {
+            None
+        }
+    }
+
+    /// Whether coordinates past one edge address the cells at the opposite edge.
+    fn is_wrapping(&self) -> bool {
+        false
+    }
+
+    /// The in-bounds ordinal neighbors of a coordinate.
+    fn neighbors(&self, coordinate: Point<Index>) -> impl Iterator<Item = Point<Index>> + '_ {
+        self.neighbors_in(coordinate, &VonNeumann)
+    }
+
+    /// The in-bounds neighbors of a coordinate under an arbitrary neighborhood. On a wrapping
+    /// grid, where a short axis can map several offsets onto the same cell, each neighbor is
+    /// listed once and the coordinate itself is left out.
+    fn neighbors_in<'a, N: Neighborhood>(
+        &'a self,
+        coordinate: Point<Index>,
+        neighborhood: &'a N,
+    ) -> impl Iterator<Item = Point<Index>> + 'a {
+        // only wrapping can map two offsets onto the same cell
+        let mut seen = self.is_wrapping().then(Vec::new);
+        neighborhood
+            .neighbors_of(coordinate)
+            .filter_map(|p| self.normalize(p))
+            .filter(move |p| match seen.as_mut() {
+                None => true,
+                Some(_) if *p == coordinate => false,
+                Some(seen) if seen.contains(p) => false,
+                Some(seen) => {
+                    seen.push(*p);
+                    true
+                }
+            })
+    }
+
+    /// The cells `from + step`, `from + 2 * step`, ... for as long as they're within the
+    /// grid's bounds. Rays don't wrap, even on a wrapping grid, and a zero `step` gives an
+    /// empty ray rather than one that never ends.
+    fn ray_iter(&self, from: Point, step: Point) -> impl Iterator<Item = Point> + '_ {
+        let first = (step != Point::new(0, 0)).then_some(from + step);
+        std::iter::successors(first, move |p| Some(*p + step)).take_while(|p| self.contains(*p))
+    }
+
+    /// Walk from `from` in increments of `step` until `stop` returns true, returning the cell
+    /// it stopped at and the number of steps taken, or None if the ray left the grid first
+    /// (or `step` is zero).
+    fn cast_ray<SF>(&self, from: Point, step: Point, stop: SF) -> Option<(Point, usize)>
+    where
+        Self: Sized,
+        SF: Fn(&Self, Point) -> bool,
+    {
+        self.ray_iter(from, step)
+            .zip(1..)
+            .find(|(p, _)| stop(self, *p))
+    }
+
+    /// Precompute `cast_ray` in the four orthogonal directions from every cell; see
+    /// `BlockerIndex`.
+    fn blocker_index<BF>(&self, blocked: BF) -> BlockerIndex
+    where
+        Self: Sized,
+        BF: Fn(&Self, Point) -> bool,
+    {
+        BlockerIndex::new(self, blocked)
+    }
+
+    /// Every cell reachable from `start` by stepping between neighbors for which `connected`
+    /// returns true. Includes `start` itself, if it's in-bounds.
+    fn flood_fill<N, CF>(
+        &self,
+        start: Point,
+        neighborhood: &N,
+        connected: CF,
+    ) -> BTreeSet<Point<Index>>
+    where
+        Self: Sized,
+        N: Neighborhood,
+        CF: Fn(&Self, Point, Point) -> bool,
+    {
+        let mut seen = BTreeSet::new();
+        let Some(start) = self.normalize(start) else {
+            return seen;
+        };
+        let mut work = vec![start];
+        seen.insert(start);
+        while let Some(point) = work.pop() {
+            for neighbor in self.neighbors_in(point, neighborhood) {
+                if !seen.contains(&neighbor) && connected(self, point, neighbor) {
+                    seen.insert(neighbor);
+                    work.push(neighbor);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Every coordinate in the bounding box, row by row, whether or not it's been written.
+    fn points(&self) -> impl Iterator<Item = Point<Index>> {
+        self.bounds()
+            .into_iter()
+            .flat_map(|bounds| Rect::from(bounds).points())
+    }
+
+    fn row_numbers(&self) -> impl Iterator<Item = Index> {
+        self.bounds().into_iter().flat_map(|(ul, lr)| ul.y..=lr.y)
+    }
+
+    fn column_numbers(&self) -> impl Iterator<Item = Index> {
+        self.bounds().into_iter().flat_map(|(ul, lr)| ul.x..=lr.x)
+    }
+
+    fn rows(&self) -> Rows<'_, Self>
+    where
+        Self: Sized,
+    {
+        Rows {
+            grid: self,
+            ys: self.bounds().map(|(ul, lr)| ul.y..=lr.y),
+        }
+    }
+
+    fn columns(&self) -> Columns<'_, Self>
+    where
+        Self: Sized,
+    {
+        Columns {
+            grid: self,
+            xs: self.bounds().map(|(ul, lr)| ul.x..=lr.x),
+        }
+    }
+
+    fn find(&self, value: &Self::Value) -> Option<Point>
+    where
+        Self::Value: PartialEq,
+    {
+        self.iter()
+            .find_map(|(p, v)| if v == *value { Some(p) } else { None })
+    }
+
+    fn dump_with<F: Fn(&Self::Value) -> char>(&self, f: F) {
+        for y in self.row_numbers() {
+            let cells = self
+                .column_numbers()
+                .map(|x| {
+                    let coordinate = Point::new(x, y);
+                    f(&self.get(coordinate).unwrap())
+                })
+                .collect::<String>();
+            println!("{}", cells);
+        }
+    }
+
+    fn save_to_image<F: Fn(&Self::Value) -> image::Rgb<u8>, P: AsRef<std::path::Path>>(
+        &self,
+        f: F,
+        path: P,
+    ) -> anyhow::Result<()> {
+        let Some((origin, lower_right)) = self.bounds() else {
+            anyhow::bail!("cannot save an empty grid");
+        };
+        let size = lower_right - origin;
+        let mut image = image::ImageBuffer::from_pixel(
+            size.x as u32 + 1,
+            size.y as u32 + 1,
+            image::Rgb([255, 255, 255]),
+        );
+        for y in self.row_numbers() {
+            for x in self.column_numbers() {
+                let point = Point::new(x, y);
+                let value = self.get(point).unwrap();
+                let point = point - origin;
+                image.put_pixel(point.x as u32, point.y as u32, f(&value));
+            }
+        }
+        image.save(path.as_ref())?;
+        Ok(())
+    }
+
+    /// Split the grid into 4-connected regions of cells for which `same_region` holds between
+    /// neighbors. Returns a grid of region labels, which index into the returned regions.
+    fn regions<SF>(&self, same_region: SF) -> (DenseGrid<usize>, Vec<Region>)
+    where
+        Self: Sized,
+        SF: Fn(&Self::Value, &Self::Value) -> bool,
+    {
+        let mut regions = vec![];
+        let Some((upper_left, lower_right)) = self.bounds() else {
+            return (
+                DenseGrid::new_with(Point::new(0, 0), Point::new(0, 0), usize::MAX),
+                regions,
+            );
+        };
+        let mut labels = DenseGrid::new_with(upper_left, lower_right, usize::MAX);
+        for y in self.row_numbers() {
+            for x in self.column_numbers() {
+                let point = Point::new(x, y);
+                if labels[point] != usize::MAX {
+                    continue;
+                }
+                let label = regions.len();
+                let cells =
+                    self.flood_fill(point, &VonNeumann, |g, a, b| match (g.get(a), g.get(b)) {
+                        (Some(a), Some(b)) => same_region(&a, &b),
+                        _ => false,
+                    });
+                for cell in cells.iter() {
+                    labels[*cell] = label;
+                }
+                regions.push(Region::new(label, cells));
+            }
+        }
+        (labels, regions)
+    }
+
+    /// Build a directed petgraph graph with a node for every cell and an edge from each cell to
+    /// each orthogonal neighbor for which `edge_rule` returns true.
+    fn to_graph<EF>(&self, edge_rule: EF) -> GridGraph<()>
+    where
+        Self: Sized,
+        EF: Fn(&Self, Point, Point) -> bool,
+    {
+        self.to_graph_in(&VonNeumann, |g, a, b| edge_rule(g, a, b).then_some(()))
+    }
+
+    /// Like `to_graph`, but undirected; each pair of neighbors is only offered to `edge_rule`
+    /// once, with the lesser point first.
+    fn to_ungraph<EF>(&self, edge_rule: EF) -> GridGraph<(), Undirected>
+    where
+        Self: Sized,
+        EF: Fn(&Self, Point, Point) -> bool,
+    {
+        self.to_graph_in(&VonNeumann, |g, a, b| edge_rule(g, a, b).then_some(()))
+    }
+
+    /// Like `to_graph`, but `edge_rule` returns the edge weight, or None for no edge.
+    fn to_weighted_graph<E, EF>(&self, edge_rule: EF) -> GridGraph<E>
+    where
+        Self: Sized,
+        EF: Fn(&Self, Point, Point) -> Option<E>,
+    {
+        self.to_graph_in(&VonNeumann, edge_rule)
+    }
+
+    /// The general form of `to_graph`: any neighborhood, weighted edges, and directed or
+    /// undirected depending on `Ty`. Every cell in the bounding box gets a node, written or not.
+    fn to_graph_in<N, E, Ty, EF>(&self, neighborhood: &N, edge_rule: EF) -> GridGraph<E, Ty>
+    where
+        Self: Sized,
+        N: Neighborhood,
+        Ty: EdgeType,
+        EF: Fn(&Self, Point, Point) -> Option<E>,
+    {
+        let mut graph = Graph::default();
+        let nodes = self
+            .points()
+            .map(|p| (p, graph.add_node(p)))
+            .collect::<BTreeMap<_, _>>();
+        for (point, node) in nodes.iter() {
+            for neighbor in self.neighbors_in(*point, neighborhood) {
+                if !Ty::is_directed() && neighbor <= *point {
+                    continue;
+                }
+                if let Some(weight) = edge_rule(self, *point, neighbor) {
+                    graph.add_edge(*node, nodes[&neighbor], weight);
+                }
+            }
+        }
+        GridGraph::new(graph, nodes)
+    }
+
+    /// Reduce a maze to a graph of junctions connected by corridors; see `JunctionGraph`.
+    fn contract_corridors<PF, KF>(
+        &self,
+        passable: PF,
+        keep: KF,
+        options: CorridorOptions,
+    ) -> JunctionGraph
+    where
+        Self: Sized,
+        PF: Fn(&Self, Point) -> bool,
+        KF: Fn(&Self, Point) -> bool,
+    {
+        JunctionGraph::build(self, passable, keep, options)
+    }
+
+    /// Run A* from `start` until reaching a cell for which `goal` returns true.
+    ///
+    /// Returns the total cost and the path taken (including both endpoints), or None if no
+    /// goal is reachable. Cells aren't revisited once expanded, so `heuristic` must be
+    /// consistent: it may never drop by more than the cost of the step between two cells, and
+    /// must be zero at every goal.
+    fn astar<MV, GF, TF, F, H>(
+        &self,
+        start: Point,
+        goal: GF,
+        traversible: TF,
+        cost: F,
+        heuristic: H,
+    ) -> Option<(MV, Vec<Point>)>
+    where
+        Self: Sized,
+        MV: std::fmt::Debug
+            + Clone
+            + Copy
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + num_traits::Zero
+            + std::ops::Add,
+        GF: Fn(&Self, Point) -> bool,
+        TF: Fn(&Self, Point) -> bool,
+        F: Fn(&Self, Point, Point) -> MV,
+        H: Fn(&Self, Point) -> MV,
+    {
+        use std::cmp::Reverse;
+
+        let start = self.normalize(start)?;
+        let mut best = BTreeMap::new();
+        let mut preds = BTreeMap::new();
+        let mut closed = BTreeSet::new();
+        let mut unvisited = BinaryHeap::new();
+        best.insert(start, MV::zero());
+        unvisited.push((Reverse(heuristic(self, start)), start));
+        while let Some((_, point)) = unvisited.pop() {
+            if !closed.insert(point) {
+                continue;
+            }
+            let current = best[&point];
+            if goal(self, point) {
+                let mut path = vec![point];
+                let mut point = point;
+                while let Some(pred) = preds.get(&point) {
+                    point = *pred;
+                    path.push(point);
+                }
+                path.reverse();
+                return Some((current, path));
+            }
+            for neighbor in self.neighbors(point) {
+                if closed.contains(&neighbor) {
+                    continue;
+                }
+                if !traversible(self, neighbor) {
+                    continue;
+                }
+                let next = current + cost(self, point, neighbor);
+                if best.get(&neighbor).map(|v| next < *v).unwrap_or(true) {
+                    best.insert(neighbor, next);
+                    preds.insert(neighbor, point);
+                    unvisited.push((Reverse(next + heuristic(self, neighbor)), neighbor));
+                }
+            }
+        }
+        None
+    }
+
+    /// A* to a single target, using Manhattan distance as the heuristic (measured around the
+    /// edges on a wrapping grid). If the metric type can't hold the largest distance across
+    /// the grid, the heuristic is dropped and this is plain Dijkstra.
+    fn astar_to<MV, TF, F>(
+        &self,
+        start: Point,
+        target: Point,
+        traversible: TF,
+        cost: F,
+    ) -> Option<(MV, Vec<Point>)>
+    where
+        Self: Sized,
+        MV: std::fmt::Debug
+            + Clone
+            + Copy
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + num_traits::Zero
+            + num_traits::FromPrimitive
+            + std::ops::Add,
+        TF: Fn(&Self, Point) -> bool,
+        F: Fn(&Self, Point, Point) -> MV,
+    {
+        let target = self.normalize(target)?;
+        let (ul, lr) = self.bounds()?;
+        let (width, height) = (lr.x.abs_diff(ul.x) + 1, lr.y.abs_diff(ul.y) + 1);
+        let wrapping = self.is_wrapping();
+        let estimate = MV::from_u64(width + height).is_some();
+        let heuristic = move |_: &Self, p: Point| {
+            if !estimate {
+                return MV::zero();
+            }
+            let (mut dx, mut dy) = (p.x.abs_diff(target.x), p.y.abs_diff(target.y));
+            if wrapping {
+                dx = dx.min(width - dx);
+                dy = dy.min(height - dy);
+            }
+            MV::from_u64(dx + dy).unwrap_or_else(MV::zero)
+        };
+        self.astar(start, |_, p| p == target, traversible, cost, heuristic)
+    }
+
+    /// Dijkstra over `(Point, S)` nodes for searches where position alone isn't enough state.
+    ///
+    /// `successors` yields `(point, state, cost)` for each move out of a node; moves that leave
+    /// the grid are dropped.
+    fn search_states<S, MV, I, SF, SI>(&self, starts: I, successors: SF) -> StateSearch<S, MV>
+    where
+        Self: Sized,
+        S: Ord + Clone + fmt::Debug,
+        MV: std::fmt::Debug
+            + Clone
+            + Copy
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + num_traits::Zero
+            + std::ops::Add,
+        I: IntoIterator<Item = (Point, S)>,
+        SF: Fn(&Self, Point, &S) -> SI,
+        SI: IntoIterator<Item = (Point, S, MV)>,
+    {
+        StateSearch::run(
+            starts
+                .into_iter()
+                .filter_map(|(p, s)| Some((self.normalize(p)?, s))),
+            |point, state| {
+                successors(self, point, state)
+                    .into_iter()
+                    .filter_map(|(p, s, c)| Some((self.normalize(p)?, s, c)))
+                    .collect::<Vec<_>>()
+            },
+        )
+    }
+
+    fn dijkstra<MV, TF, F>(
+        &self,
+        start: Point,
+        traversible: TF,
+        cost: F,
+    ) -> anyhow::Result<ShortestPaths<MV>>
+    where
+        Self: Sized,
+        MV: std::fmt::Debug
+            + Clone
+            + Copy
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + num_traits::Zero
+            + std::ops::Add,
+        F: Fn(&Self, Point, Point) -> MV,
+        TF: Fn(&Self, Point) -> bool,
+    {
+        self.dijkstra_with(start, &VonNeumann, traversible, cost)
+    }
+
+    /// Like `dijkstra`, but stepping between neighbors as defined by `neighborhood`.
+    fn dijkstra_with<N, MV, TF, F>(
+        &self,
+        start: Point,
+        neighborhood: &N,
+        traversible: TF,
+        cost: F,
+    ) -> anyhow::Result<ShortestPaths<MV>>
+    where
+        Self: Sized,
+        N: Neighborhood,
+        MV: std::fmt::Debug
+            + Clone
+            + Copy
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + num_traits::Zero
+            + std::ops::Add,
+        F: Fn(&Self, Point, Point) -> MV,
+        TF: Fn(&Self, Point) -> bool,
+    {
+        self.dijkstra_multi(std::iter::once(start), neighborhood, traversible, cost)
+    }
+
+    /// Dijkstra from several start points at once; each cell's distance is to the nearest one.
+    fn dijkstra_multi<I, N, MV, TF, F>(
+        &self,
+        starts: I,
+        neighborhood: &N,
+        traversible: TF,
+        cost: F,
+    ) -> anyhow::Result<ShortestPaths<MV>>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = Point>,
+        N: Neighborhood,
+        MV: std::fmt::Debug
+            + Clone
+            + Copy
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + num_traits::Zero
+            + std::ops::Add,
+        F: Fn(&Self, Point, Point) -> MV,
+        TF: Fn(&Self, Point) -> bool,
+    {
+        let (distances, preds) = run_dijkstra(
+            self,
+            starts,
+            neighborhood,
+            traversible,
+            cost,
+            None,
+            |pred, point, improved| {
+                if improved {
+                    *pred = Some(point);
+                }
+            },
+        )?;
+        Ok(ShortestPaths::new(distances, preds))
+    }
+
+    /// Like `dijkstra_multi`, but keeping every predecessor that reaches a cell at its optimal
+    /// cost rather than just the first one found.
+    fn dijkstra_all<I, N, MV, TF, F>(
+        &self,
+        starts: I,
+        neighborhood: &N,
+        traversible: TF,
+        cost: F,
+    ) -> anyhow::Result<AllShortestPaths<MV>>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = Point>,
+        N: Neighborhood,
+        MV: std::fmt::Debug
+            + Clone
+            + Copy
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + num_traits::Zero
+            + std::ops::Add,
+        F: Fn(&Self, Point, Point) -> MV,
+        TF: Fn(&Self, Point) -> bool,
+    {
+        let (distances, preds) = run_dijkstra(
+            self,
+            starts,
+            neighborhood,
+            traversible,
+            cost,
+            vec![],
+            |preds, point, improved| {
+                if improved {
+                    preds.clear();
+                }
+                preds.push(point);
+            },
+        )?;
+        Ok(AllShortestPaths::new(distances, preds))
+    }
+}
+
+/// The shared body of the Dijkstra variants. Each cell carries a `P` describing how it was
+/// reached; `record` updates it with a predecessor, and whether that predecessor was a strict
+/// improvement (as opposed to a tie with the best known distance).
+#[allow(clippy::type_complexity)]
+fn run_dijkstra<G, I, N, MV, TF, F, P, R>(
+    grid: &G,
+    starts: I,
+    neighborhood: &N,
+    traversible: TF,
+    cost: F,
+    empty_pred: P,
+    record: R,
+) -> anyhow::Result<(DenseGrid<DijkstraMetric<MV>>, DenseGrid<P>)>
+where
+    G: Grid,
+    I: IntoIterator<Item = Point>,
+    N: Neighborhood,
+    MV: std::fmt::Debug
+        + Clone
+        + Copy
+        + PartialEq
+        + Eq
+        + PartialOrd
+        + Ord
+        + num_traits::Zero
+        + std::ops::Add,
+    F: Fn(&G, Point, Point) -> MV,
+    TF: Fn(&G, Point) -> bool,
+    P: Clone + fmt::Debug,
+    R: Fn(&mut P, Point, bool),
+{
+    use std::cmp::Reverse;
+
+    let Some((upper_left, lower_right)) = grid.bounds() else {
+        anyhow::bail!("cannot search an empty grid");
+    };
+    let mut new = DenseGrid::new_with(upper_left, lower_right, DijkstraMetric::Infinite);
+    let mut preds = DenseGrid::new_with(upper_left, lower_right, empty_pred);
+    let mut unvisited = BinaryHeap::new();
+    let mut visited = BTreeSet::new();
+    for start in starts {
+        let Some(start) = grid.normalize(start) else {
+            anyhow::bail!("start point {} not contained in map", start);
+        };
+        new.set(start, DijkstraMetric::Finite(MV::zero()));
+        unvisited.push((Reverse(DijkstraMetric::Finite(MV::zero())), start));
+    }
+    while let Some((Reverse(DijkstraMetric::Finite(current)), point)) = unvisited.pop() {
+        if visited.contains(&point) {
+            continue;
+        }
+        for neighbor in grid.neighbors_in(point, neighborhood) {
+            if visited.contains(&neighbor) {
+                continue;
+            }
+            if !traversible(grid, neighbor) {
+                continue;
+            }
+            let distance = cost(grid, point, neighbor);
+            let next = current + distance;
+            let val = match new.get(neighbor) {
+                Some(DijkstraMetric::Finite(v)) => {
+                    if next < v {
+                        new.set(neighbor, DijkstraMetric::Finite(next));
+                        record(&mut preds[neighbor], point, true);
+                        next
+                    } else {
+                        if next == v {
+                            record(&mut preds[neighbor], point, false);
+                        }
+                        v
+                    }
+                }
+                Some(DijkstraMetric::Infinite) => {
+                    new.set(neighbor, DijkstraMetric::Finite(next));
+                    record(&mut preds[neighbor], point, true);
+                    next
+                }
+                None => {
+                    continue;
+                }
+            };
+            unvisited.push((Reverse(DijkstraMetric::Finite(val)), neighbor));
+        }
+        visited.insert(point);
+    }
+    Ok((new, preds))
+}
+
+pub struct Rows<'a, G: Grid> {
+    grid: &'a G,
+    ys: Option<RangeInclusive<Index>>,
+}
+
+impl<'a, G: Grid> Iterator for Rows<'a, G> {
+    type Item = Vec<G::Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let y = self.ys.as_mut()?.next()?;
+        let val = self
+            .grid
+            .column_numbers()
+            .map(|x| {
+                let pt = Point::new(x, y);
+                self.grid.get(pt).unwrap()
+            })
+            .collect();
+        Some(val)
+    }
+}
+
+pub struct Columns<'a, G: Grid> {
+    grid: &'a G,
+    xs: Option<RangeInclusive<Index>>,
+}
+
+impl<'a, G: Grid> Iterator for Columns<'a, G> {
+    type Item = Vec<G::Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.xs.as_mut()?.next()?;
+        let val = self
+            .grid
+            .row_numbers()
+            .map(|y| {
+                let pt = Point::new(x, y);
+                self.grid.get(pt).unwrap()
+            })
+            .collect();
+        Some(val)
+    }
+}
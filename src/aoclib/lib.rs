@@ -1,14 +1,42 @@
+mod cell_char;
+mod corridors;
 mod dijkstra_metric;
 mod dimval;
+mod direction;
 mod grid;
+mod grid_graph;
+mod grid_parse;
+mod grid_trait;
+mod neighborhood;
 pub mod petgraph_bellman_ford_multi;
 pub mod petgraph_bron_kerbosch;
 mod point;
+mod pointn;
+mod rays;
+mod rect;
+mod regions;
+mod shortest_paths;
+mod sparse_grid;
+mod state_search;
 mod vec3;
 
+pub use cell_char::CellChar;
+pub use corridors::{CorridorOptions, JunctionGraph};
 pub use dijkstra_metric::DijkstraMetric;
-pub use grid::DenseGrid;
+pub use direction::{Direction, Direction8};
 pub use grid::HasEmpty;
+pub use grid::{ColumnView, DenseGrid, GridView};
+pub use grid_graph::GridGraph;
+pub use grid_parse::{GridParseError, ParsedGrid};
+pub use grid_trait::Grid;
+pub use neighborhood::{HexAxial, Knight, Moore, Neighborhood, Offsets, VonNeumann};
 pub use point::Point;
-pub use point::Rotation;
-pub use vec3::Vec3;
+pub use point::{Reflection, Rotation};
+pub use pointn::PointN;
+pub use rays::BlockerIndex;
+pub use rect::Rect;
+pub use regions::Region;
+pub use shortest_paths::{AllShortestPaths, ShortestPaths};
+pub use sparse_grid::SparseGrid;
+pub use state_search::StateSearch;
+pub use vec3::{CubeRotation, Vec3};
@@ -0,0 +1,177 @@
+use super::point::Point;
+
+/// A rule for which cells count as adjacent to a given cell.
+///
+/// Any `Fn(Point) -> impl IntoIterator<Item = Point>` is also a neighborhood, for anything
+/// the fixed-offset ones below can't express.
+pub trait Neighborhood {
+    fn neighbors_of(&self, point: Point) -> impl Iterator<Item = Point>;
+}
+
+/// The four orthogonal neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VonNeumann;
+
+impl VonNeumann {
+    pub const OFFSETS: [Point; 4] = [
+        Point::new(1, 0),
+        Point::new(0, 1),
+        Point::new(-1, 0),
+        Point::new(0, -1),
+    ];
+}
+
+/// The four orthogonal and four diagonal neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Moore;
+
+impl Moore {
+    pub const OFFSETS: [Point; 8] = [
+        Point::new(1, 0),
+        Point::new(1, 1),
+        Point::new(0, 1),
+        Point::new(-1, 1),
+        Point::new(-1, 0),
+        Point::new(-1, -1),
+        Point::new(0, -1),
+        Point::new(1, -1),
+    ];
+}
+
+/// The eight cells a chess knight can jump to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Knight;
+
+impl Knight {
+    pub const OFFSETS: [Point; 8] = [
+        Point::new(1, 2),
+        Point::new(2, 1),
+        Point::new(2, -1),
+        Point::new(1, -2),
+        Point::new(-1, -2),
+        Point::new(-2, -1),
+        Point::new(-2, 1),
+        Point::new(-1, 2),
+    ];
+}
+
+/// The six neighbors of a hex cell in axial coordinates, with `x` as q and `y` as r.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HexAxial;
+
+impl HexAxial {
+    pub const OFFSETS: [Point; 6] = [
+        Point::new(1, 0),
+        Point::new(1, -1),
+        Point::new(0, -1),
+        Point::new(-1, 0),
+        Point::new(-1, 1),
+        Point::new(0, 1),
+    ];
+}
+
+/// An arbitrary fixed set of offsets.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Offsets(pub Vec<Point>);
+
+impl Neighborhood for VonNeumann {
+    fn neighbors_of(&self, point: Point) -> impl Iterator<Item = Point> {
+        Self::OFFSETS.into_iter().map(move |o| point + o)
+    }
+}
+
+impl Neighborhood for Moore {
+    fn neighbors_of(&self, point: Point) -> impl Iterator<Item = Point> {
+        Self::OFFSETS.into_iter().map(move |o| point + o)
+    }
+}
+
+impl Neighborhood for Knight {
+    fn neighbors_of(&self, point: Point) -> impl Iterator<Item = Point> {
+        Self::OFFSETS.into_iter().map(move |o| point + o)
+    }
+}
+
+impl Neighborhood for HexAxial {
+    fn neighbors_of(&self, point: Point) -> impl Iterator<Item = Point> {
+        Self::OFFSETS.into_iter().map(move |o| point + o)
+    }
+}
+
+impl Neighborhood for Offsets {
+    fn neighbors_of(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.0.iter().map(move |o| point + *o)
+    }
+}
+
+impl<F, I> Neighborhood for F
+where
+    F: Fn(Point) -> I,
+    I: IntoIterator<Item = Point>,
+{
+    fn neighbors_of(&self, point: Point) -> impl Iterator<Item = Point> {
+        self(point).into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HexAxial, Knight, Moore, Neighborhood, Offsets, VonNeumann};
+    use crate::Point;
+
+    #[test]
+    fn test_von_neumann_matches_ordinal_neighbors() {
+        let p = Point::new(3, -2);
+        assert_eq!(
+            VonNeumann.neighbors_of(p).collect::<Vec<_>>(),
+            p.ordinal_neighbors_array().to_vec()
+        );
+    }
+
+    #[test]
+    fn test_fixed_offsets() {
+        let origin = Point::new(0, 0);
+        assert_eq!(Moore.neighbors_of(origin).count(), 8);
+        assert!(Knight
+            .neighbors_of(origin)
+            .all(|p| p.manhattan_distance_to(origin) == 3));
+        let custom = Offsets(vec![Point::new(0, 5)]);
+        assert_eq!(
+            custom.neighbors_of(origin).collect::<Vec<_>>(),
+            vec![Point::new(0, 5)]
+        );
+    }
+
+    #[test]
+    fn test_hex_axial() {
+        let origin = Point::new(0, 0);
+        let mut offsets = HexAxial.neighbors_of(origin).collect::<Vec<_>>();
+        offsets.sort();
+        assert_eq!(
+            offsets,
+            vec![
+                Point::new(-1, 0),
+                Point::new(-1, 1),
+                Point::new(0, -1),
+                Point::new(0, 1),
+                Point::new(1, -1),
+                Point::new(1, 0),
+            ]
+        );
+        let p = Point::new(2, -3);
+        for n in HexAxial.neighbors_of(p) {
+            assert!(HexAxial.neighbors_of(n).any(|back| back == p));
+        }
+    }
+
+    #[test]
+    fn test_closure() {
+        let right_only = |p: Point| [p + Point::new(1, 0)];
+        assert_eq!(
+            right_only
+                .neighbors_of(Point::new(1, 1))
+                .collect::<Vec<_>>(),
+            vec![Point::new(2, 1)]
+        );
+    }
+}
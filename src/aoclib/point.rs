@@ -1,27 +1,32 @@
-use crate::dimval::DimVal;
-use std::cmp::Ordering;
+use crate::dimval::{inclusive_range, DimVal};
 use std::fmt;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Rotation {
     CW,
     CCW,
     Mirror,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Hash, PartialOrd)]
+/// A mirror line through the origin (or through a pivot, for `Point::reflect_about`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reflection {
+    /// Across the y axis, swapping left and right.
+    Horizontal,
+    /// Across the x axis, swapping top and bottom.
+    Vertical,
+    /// Across the line y = x; the same as `Point::transpose`.
+    Diagonal,
+    /// Across the line y = -x.
+    AntiDiagonal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Point<I: DimVal = i64> {
     pub x: I,
     pub y: I,
 }
 
-impl<I: DimVal + Eq> Eq for Point<I> {}
-
-impl<I: DimVal + Ord> Ord for Point<I> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
 impl<I: DimVal> Point<I> {
     pub const fn new(x: I, y: I) -> Self {
         Point { x, y }
@@ -35,14 +40,20 @@ impl<I: DimVal> Point<I> {
         Point::new(self.y, self.x)
     }
 
-    pub fn manhattan_distance_to(&self, other: Point<I>) -> usize {
-        ((self.x - other.x).abs() + (self.y - other.y).abs())
-            .to_u64()
-            .unwrap() as usize
+    pub fn manhattan_distance_to(&self, other: Point<I>) -> I {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// `manhattan_distance_to` as a number of unit steps, for comparing with path lengths.
+    pub fn manhattan_steps_to(&self, other: Point<I>) -> usize {
+        self.manhattan_distance_to(other)
+            .to_usize()
+            .expect("distance fits in usize")
     }
 
+    /// Rotate about the origin by a quarter turn (CW/CCW, with y pointing down) or a half
+    /// turn (Mirror).
     pub fn rotate_by(&self, r: Rotation) -> Self {
-        assert!(self.x == I::zero() || self.y == I::zero());
         let neg_one = I::zero() - I::one();
         match r {
             Rotation::Mirror => Point::new(neg_one * self.x, neg_one * self.y),
@@ -50,44 +61,146 @@ impl<I: DimVal> Point<I> {
             Rotation::CW => Point::new(neg_one * self.y, self.x),
         }
     }
+
+    pub fn rotate_about(&self, pivot: Point<I>, r: Rotation) -> Self {
+        pivot + (*self - pivot).rotate_by(r)
+    }
+
+    pub fn reflect(&self, r: Reflection) -> Self {
+        match r {
+            Reflection::Horizontal => Point::new(-self.x, self.y),
+            Reflection::Vertical => Point::new(self.x, -self.y),
+            Reflection::Diagonal => self.transpose(),
+            Reflection::AntiDiagonal => Point::new(-self.y, -self.x),
+        }
+    }
+
+    pub fn reflect_about(&self, pivot: Point<I>, r: Reflection) -> Self {
+        pivot + (*self - pivot).reflect(r)
+    }
+}
+
+impl<I: DimVal + num_traits::Euclid> Point<I> {
+    /// Reduce each coordinate into `0..modulus`, component-wise; handy for wrapping around a
+    /// torus of size `modulus`.
+    pub fn rem_euclid(&self, modulus: Point<I>) -> Self {
+        Point::new(self.x.rem_euclid(&modulus.x), self.y.rem_euclid(&modulus.y))
+    }
 }
 
-impl Point<i64> {
+impl<I: DimVal> Point<I> {
     pub fn ordinal_neighbors(&self) -> (Self, Self, Self, Self) {
-        (
-            *self + Self::new(1, 0),
-            *self + Self::new(0, 1),
-            *self + Self::new(-1, 0),
-            *self + Self::new(0, -1),
-        )
+        let [e, s, w, n] = self.ordinal_neighbors_array();
+        (e, s, w, n)
     }
 
-    pub fn ordinal_neighbors_array(&self) -> [Point; 4] {
+    /// The four orthogonal neighbors, in the same order as `VonNeumann::OFFSETS`.
+    pub fn ordinal_neighbors_array(&self) -> [Self; 4] {
+        let (zero, one) = (I::zero(), I::one());
         [
-            *self + Self::new(1, 0),
-            *self + Self::new(0, 1),
-            *self + Self::new(-1, 0),
-            *self + Self::new(0, -1),
+            *self + Self::new(one, zero),
+            *self + Self::new(zero, one),
+            *self + Self::new(-one, zero),
+            *self + Self::new(zero, -one),
         ]
     }
 
-    pub fn mirror(&self) -> Point {
-        Self::new(self.x * -1, self.y * -1)
+    /// The four orthogonal and four diagonal neighbors, in the same order as
+    /// `Moore::OFFSETS`.
+    pub fn neighbors8(&self) -> [Self; 8] {
+        let (zero, one) = (I::zero(), I::one());
+        [
+            *self + Self::new(one, zero),
+            *self + Self::new(one, one),
+            *self + Self::new(zero, one),
+            *self + Self::new(-one, one),
+            *self + Self::new(-one, zero),
+            *self + Self::new(-one, -one),
+            *self + Self::new(zero, -one),
+            *self + Self::new(one, -one),
+        ]
+    }
+
+    pub fn mirror(&self) -> Self {
+        -*self
+    }
+
+    /// The larger of the horizontal and vertical distances; i.e., the number of king moves.
+    pub fn chebyshev_distance_to(&self, other: Point<I>) -> I {
+        let (dx, dy) = ((self.x - other.x).abs(), (self.y - other.y).abs());
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+
+    pub fn euclidean_distance_squared_to(&self, other: Point<I>) -> I {
+        let delta = *self - other;
+        delta.x * delta.x + delta.y * delta.y
+    }
+
+    /// Every point within Manhattan distance `radius` of this one (a diamond), including
+    /// this one, row by row.
+    pub fn within_manhattan(&self, radius: I) -> impl Iterator<Item = Self> {
+        let center = *self;
+        inclusive_range(-radius, radius).flat_map(move |dy| {
+            let half_width = radius - dy.abs();
+            inclusive_range(-half_width, half_width).map(move |dx| center + Point::new(dx, dy))
+        })
+    }
+
+    /// Every point within Chebyshev distance `radius` of this one (a square), including this
+    /// one, row by row.
+    pub fn within_chebyshev(&self, radius: I) -> impl Iterator<Item = Self> {
+        let center = *self;
+        inclusive_range(-radius, radius).flat_map(move |dy| {
+            inclusive_range(-radius, radius).map(move |dx| center + Point::new(dx, dy))
+        })
     }
 }
 
 impl<I: DimVal + Ord> Point<I> {
+    /// Every point on the line from here to `other`, inclusive of both ends. Orthogonal and
+    /// 45° lines are exact; anything else is approximated with Bresenham's algorithm.
     pub fn line_to(&self, other: Point<I>) -> impl Iterator<Item = Point<I>> {
         LineToIter::new(*self, other)
     }
 }
 
-impl fmt::Display for Point {
+impl<I: DimVal> Point<I> {
+    /// The step from here towards `other` with each coordinate reduced to -1, 0 or 1. For
+    /// orthogonal and 45° lines, repeatedly adding this walks from one point to the other.
+    pub fn steps_to(&self, other: Point<I>) -> Point<I> {
+        let delta = other - *self;
+        Point::new(delta.x.signum(), delta.y.signum())
+    }
+}
+
+impl<I: DimVal> fmt::Display for Point<I> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
 
+impl<I: DimVal> From<(I, I)> for Point<I> {
+    fn from((x, y): (I, I)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl<I: DimVal> From<[I; 2]> for Point<I> {
+    fn from([x, y]: [I; 2]) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl<I: DimVal> From<Point<I>> for (I, I) {
+    fn from(p: Point<I>) -> Self {
+        p.as_tuple()
+    }
+}
+
 impl<I: DimVal> std::ops::Add for Point<I> {
     type Output = Self;
 
@@ -121,61 +234,125 @@ impl<I: DimVal> std::ops::Mul<I> for Point<I> {
     }
 }
 
+impl<I: DimVal> std::ops::Div<I> for Point<I> {
+    type Output = Self;
+
+    fn div(self, other: I) -> Self {
+        Point {
+            x: self.x / other,
+            y: self.y / other,
+        }
+    }
+}
+
+/// Truncating remainder of each coordinate; see `rem_euclid` for the wrap-around kind.
+impl<I: DimVal> std::ops::Rem<I> for Point<I> {
+    type Output = Self;
+
+    fn rem(self, other: I) -> Self {
+        Point {
+            x: self.x % other,
+            y: self.y % other,
+        }
+    }
+}
+
+impl<I: DimVal> std::ops::Neg for Point<I> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<I: DimVal> std::ops::AddAssign for Point<I> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<I: DimVal> std::ops::SubAssign for Point<I> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<I: DimVal> std::ops::MulAssign<I> for Point<I> {
+    fn mul_assign(&mut self, other: I) {
+        *self = *self * other;
+    }
+}
+
+impl<I: DimVal> std::iter::Sum for Point<I> {
+    fn sum<It: Iterator<Item = Self>>(iter: It) -> Self {
+        iter.fold(Point::new(I::zero(), I::zero()), |a, b| a + b)
+    }
+}
+
+impl<'a, I: DimVal> std::iter::Sum<&'a Point<I>> for Point<I> {
+    fn sum<It: Iterator<Item = &'a Self>>(iter: It) -> Self {
+        iter.copied().sum()
+    }
+}
+
 #[derive(Debug)]
 struct LineToIter<I: DimVal> {
-    start: Point<I>,
+    current: Point<I>,
     end: Point<I>,
-    direction: Point<I>,
+    step: Point<I>,
+    dx: I,
+    dy: I,
+    error: I,
     done: bool,
 }
 
 impl<I: DimVal + Ord> LineToIter<I> {
     fn new(start: Point<I>, end: Point<I>) -> Self {
-        debug_assert!(start.x == end.x || start.y == end.y);
-        let zero = I::zero();
-        let one = I::one();
-        let neg_one = one.neg();
-        let dir = match (start.x.cmp(&end.x), start.y.cmp(&end.y)) {
-            (Ordering::Less, _) => Point { x: one, y: zero },
-            (Ordering::Greater, _) => Point {
-                x: neg_one,
-                y: zero,
-            },
-            (Ordering::Equal, Ordering::Less) => Point { x: zero, y: one },
-            (Ordering::Equal, Ordering::Greater) => Point {
-                x: zero,
-                y: neg_one,
-            },
-            (Ordering::Equal, Ordering::Equal) => unreachable!(),
-        };
+        let dx = (end.x - start.x).abs();
+        let dy = -(end.y - start.y).abs();
         Self {
-            start,
+            current: start,
             end,
-            direction: dir,
+            step: start.steps_to(end),
+            dx,
+            dy,
+            error: dx + dy,
             done: false,
         }
     }
 }
 
-impl<I: DimVal> Iterator for LineToIter<I> {
+impl<I: DimVal + Ord> Iterator for LineToIter<I> {
     type Item = Point<I>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.done {
             return None;
         }
-        let current = self.start;
-        if self.start == self.end {
-            self.done = true
+        let current = self.current;
+        if current == self.end {
+            self.done = true;
+            return Some(current);
+        }
+        let doubled = self.error + self.error;
+        if doubled >= self.dy {
+            self.error = self.error + self.dy;
+            self.current.x = self.current.x + self.step.x;
+        }
+        if doubled <= self.dx {
+            self.error = self.error + self.dx;
+            self.current.y = self.current.y + self.step.y;
         }
-        self.start = self.start + self.direction;
         Some(current)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Point, Rotation};
+    use super::{Point, Reflection, Rotation};
 
     #[test]
     fn transpose() {
@@ -209,6 +386,61 @@ mod tests {
         assert_eq!(points[10], Point::new(10, 0));
     }
 
+    #[test]
+    fn test_line_to_diagonal() {
+        let points = Point::new(3, 0)
+            .line_to(Point::new(0, 3))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            vec![
+                Point::new(3, 0),
+                Point::new(2, 1),
+                Point::new(1, 2),
+                Point::new(0, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_to_arbitrary() {
+        let points = Point::new(0, 0)
+            .line_to(Point::new(6, 2))
+            .collect::<Vec<_>>();
+        assert_eq!(points.len(), 7);
+        assert_eq!(points[0], Point::new(0, 0));
+        assert_eq!(points[6], Point::new(6, 2));
+        assert!(points
+            .windows(2)
+            .all(|w| w[1].x == w[0].x + 1 && (w[1].y - w[0].y) <= 1));
+    }
+
+    #[test]
+    fn test_line_to_self() {
+        let p = Point::new(-4, 7);
+        assert_eq!(p.line_to(p).collect::<Vec<_>>(), vec![p]);
+    }
+
+    #[test]
+    fn test_steps_to() {
+        assert_eq!(
+            Point::new(1, 1).steps_to(Point::new(-5, 1)),
+            Point::new(-1, 0)
+        );
+        assert_eq!(
+            Point::new(1, 1).steps_to(Point::new(4, 9)),
+            Point::new(1, 1)
+        );
+        assert_eq!(
+            Point::new(1, 1).steps_to(Point::new(1, 1)),
+            Point::new(0, 0)
+        );
+        assert_eq!(
+            Point::<i32>::new(0, 0).steps_to(Point::new(0, -3)),
+            Point::new(0, -1)
+        );
+    }
+
     #[test]
     fn test_rotate_by_cw() {
         assert_eq!(Point::new(1, 0).rotate_by(Rotation::CW), Point::new(0, 1));
@@ -228,6 +460,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rotate_any_point() {
+        let p = Point::new(3, 1);
+        assert_eq!(p.rotate_by(Rotation::CW), Point::new(-1, 3));
+        assert_eq!(p.rotate_by(Rotation::CCW), Point::new(1, -3));
+        assert_eq!(p.rotate_by(Rotation::Mirror), Point::new(-3, -1));
+        assert_eq!(
+            p.rotate_by(Rotation::CW).rotate_by(Rotation::CW),
+            p.rotate_by(Rotation::Mirror)
+        );
+        assert_eq!(
+            p.rotate_about(Point::new(2, 2), Rotation::CW),
+            Point::new(3, 3)
+        );
+    }
+
+    #[test]
+    fn test_reflect() {
+        let p = Point::new(3, 1);
+        assert_eq!(p.reflect(Reflection::Horizontal), Point::new(-3, 1));
+        assert_eq!(p.reflect(Reflection::Vertical), Point::new(3, -1));
+        assert_eq!(p.reflect(Reflection::Diagonal), Point::new(1, 3));
+        assert_eq!(p.reflect(Reflection::AntiDiagonal), Point::new(-1, -3));
+        assert_eq!(
+            p.reflect_about(Point::new(5, 0), Reflection::Horizontal),
+            Point::new(7, 1)
+        );
+        assert_eq!(
+            p.reflect_about(Point::new(0, 2), Reflection::Diagonal),
+            Point::new(-1, 5)
+        );
+    }
+
+    #[test]
+    fn test_operators() {
+        let mut p = Point::new(7, -3);
+        assert_eq!(-p, Point::new(-7, 3));
+        assert_eq!(p / 2, Point::new(3, -1));
+        assert_eq!(p % 2, Point::new(1, -1));
+        assert_eq!(p.rem_euclid(Point::new(5, 5)), Point::new(2, 2));
+        p += Point::new(1, 1);
+        assert_eq!(p, Point::new(8, -2));
+        p -= Point::new(8, 0);
+        assert_eq!(p, Point::new(0, -2));
+        p *= 3;
+        assert_eq!(p, Point::new(0, -6));
+        let total: Point = [Point::new(1, 2), Point::new(3, 4)].iter().sum();
+        assert_eq!(total, Point::new(4, 6));
+        assert_eq!(Point::from((1, 2)), Point::from([1, 2]));
+        assert_eq!(<(i64, i64)>::from(Point::new(1, 2)), (1, 2));
+        assert_eq!(Point::<i32>::new(1, -2).to_string(), "(1, -2)");
+        assert_eq!(Point::new(0.5, 1.0).to_string(), "(0.5, 1)");
+    }
+
+    #[test]
+    fn test_distances() {
+        let p = Point::new(1, 1);
+        let q = Point::new(4, -1);
+        assert_eq!(p.manhattan_distance_to(q), 5);
+        assert_eq!(p.manhattan_steps_to(q), 5usize);
+        assert_eq!(p.chebyshev_distance_to(q), 3);
+        assert_eq!(p.euclidean_distance_squared_to(q), 13);
+        assert_eq!(
+            Point::new(0.0, 0.0).euclidean_distance_squared_to(Point::new(0.5, 1.0)),
+            1.25
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point::<i32>::new(2, 2);
+        assert_eq!(p.ordinal_neighbors_array()[3], Point::new(2, 1));
+        assert_eq!(p.neighbors8().len(), 8);
+        assert!(p
+            .neighbors8()
+            .iter()
+            .all(|n| p.chebyshev_distance_to(*n) == 1));
+
+        let diamond = p.within_manhattan(2).collect::<Vec<_>>();
+        assert_eq!(diamond.len(), 13);
+        assert!(diamond.iter().all(|n| p.manhattan_distance_to(*n) <= 2));
+        assert_eq!(p.within_manhattan(0).collect::<Vec<_>>(), vec![p]);
+
+        let square = p.within_chebyshev(1).collect::<Vec<_>>();
+        assert_eq!(square.len(), 9);
+        assert_eq!(square[0], Point::new(1, 1));
+        assert_eq!(square[8], Point::new(3, 3));
+    }
+
     #[test]
     fn test_rotate_by_mirror() {
         assert_eq!(
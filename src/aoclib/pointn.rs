@@ -0,0 +1,293 @@
+use std::fmt;
+
+use crate::dimval::DimVal;
+use crate::{Point, Vec3};
+
+/// A point (or vector) with any fixed number of dimensions. `Point` and `Vec3` convert to and
+/// from the 2- and 3-dimensional versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointN<T: DimVal, const N: usize> {
+    pub coords: [T; N],
+}
+
+impl<T: DimVal, const N: usize> PointN<T, N> {
+    pub const fn new(coords: [T; N]) -> Self {
+        PointN { coords }
+    }
+
+    pub fn zero() -> Self {
+        PointN::new([T::zero(); N])
+    }
+
+    fn zip_with<F: Fn(T, T) -> T>(&self, other: &Self, f: F) -> Self {
+        PointN::new(std::array::from_fn(|i| f(self.coords[i], other.coords[i])))
+    }
+
+    fn map<F: Fn(T) -> T>(&self, f: F) -> Self {
+        PointN::new(self.coords.map(f))
+    }
+
+    pub fn manhattan_norm(&self) -> T {
+        self.coords
+            .iter()
+            .fold(T::zero(), |total, c| total + c.abs())
+    }
+
+    pub fn chebyshev_norm(&self) -> T {
+        self.coords.iter().fold(
+            T::zero(),
+            |largest, c| {
+                if c.abs() > largest {
+                    c.abs()
+                } else {
+                    largest
+                }
+            },
+        )
+    }
+
+    pub fn manhattan_distance_to(&self, other: Self) -> T {
+        (*self - other).manhattan_norm()
+    }
+
+    pub fn chebyshev_distance_to(&self, other: Self) -> T {
+        (*self - other).chebyshev_norm()
+    }
+
+    pub fn euclidean_distance_squared_to(&self, other: Self) -> T {
+        (*self - other)
+            .coords
+            .iter()
+            .fold(T::zero(), |total, c| total + *c * *c)
+    }
+
+    /// The 2N points one step away along a single axis.
+    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = Self> {
+        let center = *self;
+        (0..N).flat_map(move |axis| {
+            [-T::one(), T::one()].map(|step| {
+                let mut p = center;
+                p.coords[axis] = p.coords[axis] + step;
+                p
+            })
+        })
+    }
+
+    /// All 3^N - 1 points that differ from this one by at most one in every coordinate.
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> {
+        let center = *self;
+        (0..3usize.pow(N as u32)).filter_map(move |mut digits| {
+            let mut offset = Self::zero();
+            for c in offset.coords.iter_mut() {
+                *c = match digits % 3 {
+                    0 => T::zero(),
+                    1 => T::one(),
+                    _ => -T::one(),
+                };
+                digits /= 3;
+            }
+            (offset != Self::zero()).then(|| center + offset)
+        })
+    }
+}
+
+impl<T: DimVal + num_traits::Euclid, const N: usize> PointN<T, N> {
+    /// Reduce each coordinate into `0..modulus`, component-wise.
+    pub fn rem_euclid(&self, modulus: Self) -> Self {
+        self.zip_with(&modulus, |a, m| a.rem_euclid(&m))
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::Index<usize> for PointN<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.coords[axis]
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::IndexMut<usize> for PointN<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.coords[axis]
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::Add for PointN<T, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip_with(&other, |a, b| a + b)
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::Sub for PointN<T, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip_with(&other, |a, b| a - b)
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::Mul<T> for PointN<T, N> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        self.map(|a| a * scalar)
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::Div<T> for PointN<T, N> {
+    type Output = Self;
+
+    fn div(self, scalar: T) -> Self {
+        self.map(|a| a / scalar)
+    }
+}
+
+/// Truncating remainder of each coordinate; see `rem_euclid` for the wrap-around kind.
+impl<T: DimVal, const N: usize> std::ops::Rem<T> for PointN<T, N> {
+    type Output = Self;
+
+    fn rem(self, scalar: T) -> Self {
+        self.map(|a| a % scalar)
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::Neg for PointN<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::AddAssign for PointN<T, N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::SubAssign for PointN<T, N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: DimVal, const N: usize> std::ops::MulAssign<T> for PointN<T, N> {
+    fn mul_assign(&mut self, scalar: T) {
+        *self = *self * scalar;
+    }
+}
+
+impl<T: DimVal, const N: usize> std::iter::Sum for PointN<T, N> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |a, b| a + b)
+    }
+}
+
+impl<'a, T: DimVal, const N: usize> std::iter::Sum<&'a PointN<T, N>> for PointN<T, N> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<T: DimVal, const N: usize> From<[T; N]> for PointN<T, N> {
+    fn from(coords: [T; N]) -> Self {
+        PointN::new(coords)
+    }
+}
+
+impl<T: DimVal> From<Point<T>> for PointN<T, 2> {
+    fn from(p: Point<T>) -> Self {
+        PointN::new([p.x, p.y])
+    }
+}
+
+impl<T: DimVal> From<PointN<T, 2>> for Point<T> {
+    fn from(p: PointN<T, 2>) -> Self {
+        Point::new(p.coords[0], p.coords[1])
+    }
+}
+
+impl<T: DimVal> From<Vec3<T>> for PointN<T, 3> {
+    fn from(v: Vec3<T>) -> Self {
+        PointN::new(v.as_array())
+    }
+}
+
+impl<T: DimVal> From<PointN<T, 3>> for Vec3<T> {
+    fn from(p: PointN<T, 3>) -> Self {
+        Vec3::from(p.coords)
+    }
+}
+
+impl<T: DimVal, const N: usize> fmt::Display for PointN<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.coords.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PointN;
+    use crate::{Point, Vec3};
+
+    #[test]
+    fn test_arithmetic() {
+        let a = PointN::new([1, 2, 3, 4]);
+        let b = PointN::new([4, 3, 2, 1]);
+        assert_eq!(a + b, PointN::new([5, 5, 5, 5]));
+        assert_eq!(a - b, PointN::new([-3, -1, 1, 3]));
+        assert_eq!(-a * 2, PointN::new([-2, -4, -6, -8]));
+        assert_eq!(b / 2, PointN::new([2, 1, 1, 0]));
+        assert_eq!(
+            (-b).rem_euclid(PointN::new([3; 4])),
+            PointN::new([2, 0, 1, 2])
+        );
+        assert_eq!([a, b].iter().sum::<PointN<i64, 4>>()[0], 5);
+        assert_eq!(a.to_string(), "(1, 2, 3, 4)");
+        assert!(a < b);
+        assert_eq!([b, a].iter().max(), Some(&b));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = PointN::new([1, 2, 3, 4]);
+        let b = PointN::new([4, 3, 2, 1]);
+        assert_eq!(a.manhattan_distance_to(b), 8);
+        assert_eq!(a.chebyshev_distance_to(b), 3);
+        assert_eq!(a.euclidean_distance_squared_to(b), 20);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let origin = PointN::<i64, 4>::zero();
+        assert_eq!(origin.neighbors().count(), 80);
+        assert!(origin
+            .neighbors()
+            .all(|n| origin.chebyshev_distance_to(n) == 1));
+        assert_eq!(origin.orthogonal_neighbors().count(), 8);
+        assert!(origin
+            .orthogonal_neighbors()
+            .all(|n| origin.manhattan_distance_to(n) == 1));
+        let p = PointN::from(Point::new(3, 4));
+        assert_eq!(p.neighbors().count(), 8);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let p = Point::new(3, -4);
+        assert_eq!(Point::from(PointN::from(p)), p);
+        let v = Vec3::new(1, 2, 3);
+        let n: PointN<i64, 3> = v.into();
+        assert_eq!(n[2], 3);
+        assert_eq!(Vec3::from(n), v);
+    }
+}
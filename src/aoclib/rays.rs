@@ -0,0 +1,121 @@
+use super::point::Point;
+use super::{DenseGrid, Grid, VonNeumann};
+
+/// For every cell and each orthogonal direction, the first blocked cell you'd hit moving that
+/// way, so that repeated `Grid::cast_ray`s against a fixed set of blockers are O(1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockerIndex {
+    // indexed in the same order as `VonNeumann::OFFSETS`
+    next: [DenseGrid<Option<Point>>; 4],
+}
+
+impl BlockerIndex {
+    pub fn new<G, BF>(grid: &G, blocked: BF) -> Self
+    where
+        G: Grid,
+        BF: Fn(&G, Point) -> bool,
+    {
+        let (ul, lr) = grid
+            .bounds()
+            .unwrap_or((Point::new(0, 0), Point::new(0, 0)));
+        let mut points = grid.points().collect::<Vec<_>>();
+        let next = VonNeumann::OFFSETS.map(|step| {
+            let mut next = DenseGrid::new_with(ul, lr, None);
+            // visit cells furthest along `step` first, so the answer for the cell after each
+            // one is already known
+            points.sort_by_key(|p| -(p.x * step.x + p.y * step.y));
+            for point in points.iter() {
+                let ahead = *point + step;
+                let hit = if !grid.contains(ahead) {
+                    None
+                } else if blocked(grid, ahead) {
+                    Some(ahead)
+                } else {
+                    next[ahead]
+                };
+                next.set(*point, hit);
+            }
+            next
+        });
+        BlockerIndex { next }
+    }
+
+    fn direction_index(step: Point) -> usize {
+        VonNeumann::OFFSETS
+            .iter()
+            .position(|o| *o == step)
+            .expect("blocker indexes only handle unit orthogonal steps")
+    }
+
+    /// The first blocked cell strictly after `from` in direction `step`, which must be one of
+    /// `VonNeumann::OFFSETS`. Returns None if the ray leaves the grid first.
+    pub fn next_blocker(&self, from: Point, step: Point) -> Option<Point> {
+        self.next[Self::direction_index(step)].get(from).flatten()
+    }
+
+    /// Same as `Grid::cast_ray` with the predicate this index was built from.
+    pub fn cast_ray(&self, from: Point, step: Point) -> Option<(Point, usize)> {
+        let hit = self.next_blocker(from, step)?;
+        Some((hit, hit.manhattan_steps_to(from)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DenseGrid, Grid, Point, SparseGrid, VonNeumann};
+
+    #[test]
+    fn test_rays() {
+        let g = DenseGrid::from_input("....#\n.#...\n.....\n#...#", |c| c == '#');
+        let blocked = |g: &DenseGrid<bool>, p| g.get(p) == Some(true);
+        assert_eq!(
+            g.ray_iter(Point::new(1, 1), Point::new(1, 1))
+                .collect::<Vec<_>>(),
+            vec![Point::new(2, 2), Point::new(3, 3)]
+        );
+        assert_eq!(
+            g.cast_ray(Point::new(0, 0), Point::new(1, 0), blocked),
+            Some((Point::new(4, 0), 4))
+        );
+        assert_eq!(
+            g.cast_ray(Point::new(0, 0), Point::new(0, 1), blocked),
+            Some((Point::new(0, 3), 3))
+        );
+        assert_eq!(
+            g.cast_ray(Point::new(2, 0), Point::new(0, 1), blocked),
+            None
+        );
+        assert_eq!(g.ray_iter(Point::new(1, 1), Point::new(0, 0)).count(), 0);
+        assert_eq!(
+            g.cast_ray(Point::new(1, 1), Point::new(0, 0), |_, _| true),
+            None
+        );
+
+        let index = g.blocker_index(blocked);
+        for (point, _) in g.iter() {
+            for step in VonNeumann::OFFSETS {
+                assert_eq!(
+                    index.cast_ray(point, step),
+                    g.cast_ray(point, step, blocked)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sparse_blockers() {
+        // unwritten cells read as 0, and count as blockers
+        let mut g = SparseGrid::<u8>::new();
+        g.set(Point::new(0, 0), 1);
+        g.set(Point::new(3, 0), 1);
+        let index = g.blocker_index(|g, p| g.get(p) == Some(0));
+        assert_eq!(
+            index.next_blocker(Point::new(1, 0), Point::new(1, 0)),
+            Some(Point::new(2, 0))
+        );
+        assert_eq!(
+            index.next_blocker(Point::new(3, 0), Point::new(-1, 0)),
+            Some(Point::new(2, 0))
+        );
+    }
+}
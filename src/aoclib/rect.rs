@@ -0,0 +1,181 @@
+use std::cmp::{max, min};
+use std::fmt;
+
+use super::point::Point;
+
+type Index = i64;
+
+/// An axis-aligned rectangle of grid cells, inclusive of both corners. Never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    /// The rectangle spanned by two opposite corners, in either order.
+    pub fn new(a: Point, b: Point) -> Self {
+        Rect {
+            min: Point::new(min(a.x, b.x), min(a.y, b.y)),
+            max: Point::new(max(a.x, b.x), max(a.y, b.y)),
+        }
+    }
+
+    /// The rectangle with its upper-left corner at `origin` covering `width` by `height`
+    /// cells, or None if either is zero.
+    pub fn with_size(origin: Point, width: usize, height: usize) -> Option<Self> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some(Rect {
+            min: origin,
+            max: origin + Point::new(width as Index - 1, height as Index - 1),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        1 + self.max.x.abs_diff(self.min.x) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        1 + self.max.y.abs_diff(self.min.y) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    /// The cells in both rectangles, or None if they don't overlap.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let lo = Point::new(max(self.min.x, other.min.x), max(self.min.y, other.min.y));
+        let hi = Point::new(min(self.max.x, other.max.x), min(self.max.y, other.max.y));
+        (lo.x <= hi.x && lo.y <= hi.y).then_some(Rect { min: lo, max: hi })
+    }
+
+    /// The smallest rectangle covering both.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: Point::new(min(self.min.x, other.min.x), min(self.min.y, other.min.y)),
+            max: Point::new(max(self.max.x, other.max.x), max(self.max.y, other.max.y)),
+        }
+    }
+
+    /// Grow by `by` cells on every side.
+    pub fn expand(&self, by: usize) -> Rect {
+        let by = Point::new(by as Index, by as Index);
+        Rect {
+            min: self.min - by,
+            max: self.max + by,
+        }
+    }
+
+    /// Every cell, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (lo, hi) = (self.min, self.max);
+        (lo.y..=hi.y).flat_map(move |y| (lo.x..=hi.x).map(move |x| Point::new(x, y)))
+    }
+
+    /// Split into upper-left, upper-right, lower-left and lower-right quarters. When the width
+    /// or height is odd, the middle column or row belongs to no quarter; quarters that would
+    /// have no cells at all are None.
+    pub fn quadrants(&self) -> [Option<Rect>; 4] {
+        let (half_w, half_h) = ((self.width() / 2) as Index, (self.height() / 2) as Index);
+        let left = (self.min.x, self.min.x + half_w - 1);
+        let right = (self.max.x - half_w + 1, self.max.x);
+        let top = (self.min.y, self.min.y + half_h - 1);
+        let bottom = (self.max.y - half_h + 1, self.max.y);
+        [(left, top), (right, top), (left, bottom), (right, bottom)].map(|((x0, x1), (y0, y1))| {
+            (half_w > 0 && half_h > 0).then_some(Rect {
+                min: Point::new(x0, y0),
+                max: Point::new(x1, y1),
+            })
+        })
+    }
+}
+
+impl From<(Point, Point)> for Rect {
+    fn from((a, b): (Point, Point)) -> Self {
+        Rect::new(a, b)
+    }
+}
+
+impl From<Rect> for (Point, Point) {
+    fn from(r: Rect) -> Self {
+        (r.min, r.max)
+    }
+}
+
+impl fmt::Display for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rect;
+    use crate::Point;
+
+    #[test]
+    fn test_rect() {
+        let r = Rect::new(Point::new(3, 4), Point::new(0, 1));
+        assert_eq!(r.min, Point::new(0, 1));
+        assert_eq!((r.width(), r.height(), r.area()), (4, 4, 16));
+        assert!(r.contains(Point::new(3, 1)));
+        assert!(!r.contains(Point::new(4, 1)));
+        assert_eq!(r.points().count(), 16);
+        assert_eq!(r.points().nth(4), Some(Point::new(0, 2)));
+        assert_eq!(Rect::with_size(Point::new(0, 1), 4, 4), Some(r));
+        assert_eq!(Rect::with_size(Point::new(0, 1), 0, 4), None);
+
+        let other = Rect::new(Point::new(2, 3), Point::new(6, 6));
+        assert_eq!(
+            r.intersection(&other),
+            Some(Rect::new(Point::new(2, 3), Point::new(3, 4)))
+        );
+        assert_eq!(
+            r.union(&other),
+            Rect::new(Point::new(0, 1), Point::new(6, 6))
+        );
+        assert_eq!(
+            r.intersection(&Rect::new(Point::new(5, 5), Point::new(5, 5))),
+            None
+        );
+        assert_eq!(r.expand(1), Rect::new(Point::new(-1, 0), Point::new(4, 5)));
+        assert!(r.expand(1).contains_rect(&r));
+        assert!(!r.contains_rect(&other));
+    }
+
+    #[test]
+    fn test_quadrants() {
+        let r = Rect::new(Point::new(0, 0), Point::new(10, 6));
+        let [ul, ur, ll, lr] = r.quadrants().map(Option::unwrap);
+        assert_eq!(ul, Rect::new(Point::new(0, 0), Point::new(4, 2)));
+        assert_eq!(ur, Rect::new(Point::new(6, 0), Point::new(10, 2)));
+        assert_eq!(ll, Rect::new(Point::new(0, 4), Point::new(4, 6)));
+        assert_eq!(lr, Rect::new(Point::new(6, 4), Point::new(10, 6)));
+
+        let even = Rect::new(Point::new(0, 0), Point::new(3, 3));
+        assert_eq!(
+            even.quadrants()
+                .iter()
+                .flatten()
+                .map(|q| q.area())
+                .sum::<usize>(),
+            16
+        );
+        let thin = Rect::new(Point::new(0, 0), Point::new(0, 3));
+        assert_eq!(thin.quadrants(), [None; 4]);
+    }
+}
@@ -0,0 +1,112 @@
+use std::cmp::{max, min};
+use std::collections::BTreeSet;
+
+use super::point::Point;
+
+/// A connected set of cells, as produced by `Grid::regions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub label: usize,
+    cells: BTreeSet<Point>,
+}
+
+impl Region {
+    pub fn new(label: usize, cells: BTreeSet<Point>) -> Self {
+        Self { label, cells }
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of unit-length fence segments separating this region from everything else,
+    /// including any holes inside it.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|p| {
+                p.ordinal_neighbors_array()
+                    .iter()
+                    .filter(|n| !self.cells.contains(n))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The number of straight fence runs around this region (and around any holes in it).
+    ///
+    /// Each fence segment faces outward in one of four directions; a segment starts a new side
+    /// unless the cell next to it along the fence is also in the region with a fence facing the
+    /// same way.
+    pub fn sides(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|p| {
+                p.ordinal_neighbors_array()
+                    .iter()
+                    .filter(|n| !self.cells.contains(n))
+                    .filter(|n| {
+                        let outward = **n - *p;
+                        let along = Point::new(outward.y, outward.x);
+                        let prev = *p - along;
+                        !self.cells.contains(&prev) || self.cells.contains(&(prev + outward))
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The upper-left and lower-right corners (inclusive) of the smallest box holding the
+    /// region.
+    pub fn bounding_box(&self) -> (Point, Point) {
+        let first = *self.cells.first().expect("regions are never empty");
+        self.cells.iter().fold((first, first), |(ul, lr), p| {
+            (
+                Point::new(min(ul.x, p.x), min(ul.y, p.y)),
+                Point::new(max(lr.x, p.x), max(lr.y, p.y)),
+            )
+        })
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.iter().copied()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains(&point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DenseGrid, Grid, Point};
+
+    #[test]
+    fn test_regions() {
+        let g = DenseGrid::from_input("AAAA\nBBCD\nBBCC\nEEEC", |c| c);
+        let (labels, regions) = g.regions(|a, b| a == b);
+        assert_eq!(regions.len(), 5);
+        let a = &regions[labels[Point::new(0, 0)]];
+        assert_eq!(a.area(), 4);
+        assert_eq!(a.perimeter(), 10);
+        assert_eq!(a.sides(), 4);
+        let c = &regions[labels[Point::new(2, 1)]];
+        assert_eq!(c.area(), 4);
+        assert_eq!(c.perimeter(), 10);
+        assert_eq!(c.sides(), 8);
+        assert_eq!(c.bounding_box(), (Point::new(2, 1), Point::new(3, 3)));
+        assert!(c.contains(Point::new(3, 3)));
+        assert_eq!(labels[Point::new(3, 2)], labels[Point::new(2, 1)]);
+    }
+
+    #[test]
+    fn test_holes() {
+        let g = DenseGrid::from_input("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA", |c| c);
+        let (labels, regions) = g.regions(|a, b| a == b);
+        assert_eq!(regions.len(), 3);
+        let outer = &regions[labels[Point::new(0, 0)]];
+        assert_eq!(outer.area(), 28);
+        assert_eq!(outer.perimeter(), 40);
+        assert_eq!(outer.sides(), 12);
+    }
+}
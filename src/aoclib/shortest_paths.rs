@@ -0,0 +1,212 @@
+use std::collections::BTreeSet;
+
+use super::point::Point;
+use super::{DenseGrid, DijkstraMetric};
+
+/// The result of a single-source grid search: distances to every cell plus the predecessor
+/// each cell was reached from.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<MV>
+where
+    MV: std::fmt::Debug + Clone + Copy,
+{
+    pub distances: DenseGrid<DijkstraMetric<MV>>,
+    pub predecessors: DenseGrid<Option<Point>>,
+}
+
+impl<MV> ShortestPaths<MV>
+where
+    MV: std::fmt::Debug + Clone + Copy + Ord,
+{
+    pub fn new(
+        distances: DenseGrid<DijkstraMetric<MV>>,
+        predecessors: DenseGrid<Option<Point>>,
+    ) -> Self {
+        Self {
+            distances,
+            predecessors,
+        }
+    }
+
+    /// Distance from the start to `target`, or None if it's unreachable or out-of-bounds.
+    pub fn distance_to(&self, target: Point) -> Option<MV> {
+        match self.distances.get(target)? {
+            DijkstraMetric::Finite(d) => Some(d),
+            DijkstraMetric::Infinite => None,
+        }
+    }
+
+    pub fn is_reachable(&self, target: Point) -> bool {
+        self.distance_to(target).is_some()
+    }
+
+    /// The cells along a shortest path from the start to `target`, including both ends.
+    pub fn path_to(&self, target: Point) -> Option<Vec<Point>> {
+        self.distance_to(target)?;
+        let mut path = vec![target];
+        let mut current = target;
+        while let Some(pred) = self.predecessors.get(current)? {
+            path.push(pred);
+            current = pred;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All reachable cells, nearest first. Ties are broken by coordinate.
+    pub fn by_distance(&self) -> impl Iterator<Item = (Point, MV)> {
+        let mut cells = self
+            .distances
+            .iter()
+            .filter_map(|(p, d)| match d {
+                DijkstraMetric::Finite(d) => Some((p, *d)),
+                DijkstraMetric::Infinite => None,
+            })
+            .collect::<Vec<_>>();
+        cells.sort_by(|(lp, ld), (rp, rd)| ld.cmp(rd).then(lp.cmp(rp)));
+        cells.into_iter()
+    }
+
+    pub fn into_parts(self) -> (DenseGrid<DijkstraMetric<MV>>, DenseGrid<Option<Point>>) {
+        (self.distances, self.predecessors)
+    }
+}
+
+/// Like `ShortestPaths`, but remembering every predecessor that reaches each cell at its
+/// optimal cost, so all tied shortest paths can be recovered.
+#[derive(Debug, Clone)]
+pub struct AllShortestPaths<MV>
+where
+    MV: std::fmt::Debug + Clone + Copy,
+{
+    pub distances: DenseGrid<DijkstraMetric<MV>>,
+    pub predecessors: DenseGrid<Vec<Point>>,
+}
+
+impl<MV> AllShortestPaths<MV>
+where
+    MV: std::fmt::Debug + Clone + Copy + Ord,
+{
+    pub fn new(
+        distances: DenseGrid<DijkstraMetric<MV>>,
+        predecessors: DenseGrid<Vec<Point>>,
+    ) -> Self {
+        Self {
+            distances,
+            predecessors,
+        }
+    }
+
+    /// Distance from the nearest start to `target`, or None if it's unreachable or
+    /// out-of-bounds.
+    pub fn distance_to(&self, target: Point) -> Option<MV> {
+        match self.distances.get(target)? {
+            DijkstraMetric::Finite(d) => Some(d),
+            DijkstraMetric::Infinite => None,
+        }
+    }
+
+    /// Every cell lying on some shortest path to whichever of `targets` is closest. Targets
+    /// that are further away than the closest one are ignored.
+    pub fn cells_on_shortest_paths<I>(&self, targets: I) -> BTreeSet<Point>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let targets = targets
+            .into_iter()
+            .filter_map(|t| self.distance_to(t).map(|d| (t, d)))
+            .collect::<Vec<_>>();
+        let mut seen = BTreeSet::new();
+        let Some(best) = targets.iter().map(|(_, d)| *d).min() else {
+            return seen;
+        };
+        let mut work = targets
+            .into_iter()
+            .filter(|(_, d)| *d == best)
+            .map(|(t, _)| t)
+            .collect::<Vec<_>>();
+        while let Some(point) = work.pop() {
+            if !seen.insert(point) {
+                continue;
+            }
+            work.extend(self.predecessors[point].iter().copied());
+        }
+        seen
+    }
+
+    /// Collapse to a single predecessor per cell.
+    pub fn to_shortest_paths(&self) -> ShortestPaths<MV> {
+        let mut predecessors = DenseGrid::new_with_dimensions_from(&self.predecessors, None);
+        for (point, preds) in self.predecessors.iter() {
+            predecessors.set(point, preds.first().copied());
+        }
+        ShortestPaths::new(self.distances.clone(), predecessors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DenseGrid, Grid, Point};
+
+    #[test]
+    fn test_paths() {
+        let g = DenseGrid::from_input("..#\n#..\n...", |c| c == '.');
+        let paths = g
+            .dijkstra(
+                Point::new(0, 0),
+                |g, p| g.get(p) == Some(true),
+                |_, _, _| 1usize,
+            )
+            .expect("should evaluate");
+        assert_eq!(paths.distance_to(Point::new(2, 2)), Some(4));
+        assert_eq!(paths.distance_to(Point::new(2, 0)), None);
+        assert_eq!(paths.distance_to(Point::new(9, 9)), None);
+        assert_eq!(
+            paths.path_to(Point::new(0, 0)),
+            Some(vec![Point::new(0, 0)])
+        );
+        let path = paths.path_to(Point::new(0, 2)).unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!(path[0], Point::new(0, 0));
+        assert_eq!(path[4], Point::new(0, 2));
+        assert_eq!(paths.path_to(Point::new(2, 0)), None);
+
+        let ordered = paths.by_distance().collect::<Vec<_>>();
+        assert_eq!(ordered.len(), 7);
+        assert_eq!(ordered[0], (Point::new(0, 0), 0));
+        assert!(ordered.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+
+    #[test]
+    fn test_all_paths() {
+        use crate::VonNeumann;
+        let g = DenseGrid::from_input("...\n.#.\n...\n#..", |c| c == '.');
+        let open = |g: &DenseGrid<bool>, p| g.get(p) == Some(true);
+        let paths = g
+            .dijkstra_all([Point::new(0, 0)], &VonNeumann, open, |_, _, _| 1usize)
+            .expect("should evaluate");
+        assert_eq!(paths.distance_to(Point::new(2, 2)), Some(4));
+        assert_eq!(paths.predecessors[Point::new(2, 2)].len(), 2);
+        // both ways around the pillar are optimal
+        assert_eq!(paths.cells_on_shortest_paths([Point::new(2, 2)]).len(), 8);
+        // the further target is ignored
+        assert_eq!(
+            paths
+                .cells_on_shortest_paths([Point::new(2, 3), Point::new(2, 0)])
+                .len(),
+            3
+        );
+
+        let multi = g
+            .dijkstra_multi(
+                [Point::new(0, 0), Point::new(2, 3)],
+                &VonNeumann,
+                open,
+                |_, _, _| 1usize,
+            )
+            .expect("should evaluate");
+        assert_eq!(multi.distance_to(Point::new(2, 1)), Some(2));
+        assert_eq!(multi.distance_to(Point::new(0, 2)), Some(2));
+        assert_eq!(multi.path_to(Point::new(1, 3)).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,287 @@
+use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::fmt;
+
+use super::point::Point;
+use super::{DenseGrid, Grid, HasEmpty};
+
+type Index = i64;
+
+/// A hash-backed grid over an unbounded plane.
+///
+/// Cells that have never been written read back as the grid's empty value, as long as they're
+/// inside the bounding box; the bounding box grows to cover every cell that gets written.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<V: Clone + fmt::Debug> {
+    bounds: Option<(Point<Index>, Point<Index>)>,
+    empty_value: V,
+    cells: HashMap<Point<Index>, V>,
+}
+
+impl<V: Clone + fmt::Debug + HasEmpty> SparseGrid<V> {
+    pub fn new() -> Self {
+        Self::new_with(V::empty_value())
+    }
+}
+
+impl<V: Clone + fmt::Debug + HasEmpty + PartialEq> SparseGrid<V> {
+    /// Parse a block of text the same way as `DenseGrid::from_input`. The bounding box covers
+    /// the whole input, but cells that map to the empty value aren't stored.
+    pub fn from_input<F>(input: &str, f: F) -> Self
+    where
+        F: Fn(char) -> V,
+    {
+        let empty = V::empty_value();
+        let mut g = Self::new();
+        for (y, row) in input.lines().enumerate() {
+            for (x, chr) in row.chars().enumerate() {
+                let coord = Point::new(x as i64, y as i64);
+                g.include(coord);
+                let value = f(chr);
+                if value != empty {
+                    g.set(coord, value);
+                }
+            }
+        }
+        g
+    }
+}
+
+impl<V: Clone + fmt::Debug + HasEmpty> Default for SparseGrid<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone + fmt::Debug> SparseGrid<V> {
+    pub fn new_with(empty_value: V) -> Self {
+        Self {
+            bounds: None,
+            empty_value,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Build a sparse grid holding every cell of a dense one, with the same bounds.
+    pub fn from_dense(g: &DenseGrid<V>, empty_value: V) -> Self {
+        let mut new = Self::new_with(empty_value);
+        new.bounds = Some(g.rect().into());
+        new.cells.extend(g.iter().map(|(p, v)| (p, v.clone())));
+        new
+    }
+
+    /// Convert into a dense grid covering the bounding box; unwritten cells get the empty value.
+    ///
+    /// Returns None if nothing has ever been written.
+    pub fn to_dense(&self) -> Option<DenseGrid<V>> {
+        let (upper_left, lower_right) = self.bounds?;
+        let mut g = DenseGrid::new_with(upper_left, lower_right, self.empty_value.clone());
+        for (point, value) in self.cells.iter() {
+            g.set(*point, value.clone());
+        }
+        Some(g)
+    }
+
+    pub fn empty_value(&self) -> &V {
+        &self.empty_value
+    }
+
+    pub fn min_x(&self) -> Option<Index> {
+        self.bounds.map(|(ul, _)| ul.x)
+    }
+
+    pub fn min_y(&self) -> Option<Index> {
+        self.bounds.map(|(ul, _)| ul.y)
+    }
+
+    pub fn max_x(&self) -> Option<Index> {
+        self.bounds.map(|(_, lr)| lr.x)
+    }
+
+    pub fn max_y(&self) -> Option<Index> {
+        self.bounds.map(|(_, lr)| lr.y)
+    }
+
+    pub fn origin(&self) -> Option<Point> {
+        self.bounds.map(|(ul, _)| ul)
+    }
+
+    pub fn row_numbers(&self) -> impl Iterator<Item = Index> {
+        self.bounds.into_iter().flat_map(|(ul, lr)| ul.y..=lr.y)
+    }
+
+    pub fn column_numbers(&self) -> impl Iterator<Item = Index> {
+        self.bounds.into_iter().flat_map(|(ul, lr)| ul.x..=lr.x)
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map(|(ul, lr)| 1 + lr.x.abs_diff(ul.x) as usize)
+            .unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map(|(ul, lr)| 1 + lr.y.abs_diff(ul.y) as usize)
+            .unwrap_or(0)
+    }
+
+    /// The number of cells inside the bounding box, whether or not they've been written.
+    pub fn size(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    /// The number of cells that have actually been written.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Get a value by coordinate. Returns None if the coordinate is outside the bounding box.
+    pub fn get(&self, coordinate: Point<Index>) -> Option<V> {
+        if !self.contains(coordinate) {
+            return None;
+        }
+        Some(
+            self.cells
+                .get(&coordinate)
+                .unwrap_or(&self.empty_value)
+                .clone(),
+        )
+    }
+
+    /// Set a value by coordinate, growing the bounding box if necessary. Always succeeds.
+    pub fn set(&mut self, coordinate: Point<Index>, value: V) -> Option<()> {
+        self.include(coordinate);
+        self.cells.insert(coordinate, value);
+        Some(())
+    }
+
+    /// Forget a written value. The bounding box never shrinks.
+    pub fn remove(&mut self, coordinate: Point<Index>) -> Option<V> {
+        self.cells.remove(&coordinate)
+    }
+
+    /// Whether a value has been written at the coordinate.
+    pub fn is_set(&self, coordinate: Point<Index>) -> bool {
+        self.cells.contains_key(&coordinate)
+    }
+
+    /// Grow the bounding box to include the given coordinate without writing anything.
+    pub fn include(&mut self, coordinate: Point<Index>) {
+        self.bounds = Some(match self.bounds {
+            Some((ul, lr)) => (
+                Point::new(min(ul.x, coordinate.x), min(ul.y, coordinate.y)),
+                Point::new(max(lr.x, coordinate.x), max(lr.y, coordinate.y)),
+            ),
+            None => (coordinate, coordinate),
+        });
+    }
+}
+
+impl<V: Clone + fmt::Debug> Grid for SparseGrid<V> {
+    type Value = V;
+
+    fn bounds(&self) -> Option<(Point<Index>, Point<Index>)> {
+        self.bounds
+    }
+
+    fn get(&self, coordinate: Point<Index>) -> Option<V> {
+        SparseGrid::get(self, coordinate)
+    }
+
+    fn set(&mut self, coordinate: Point<Index>, value: V) -> Option<()> {
+        SparseGrid::set(self, coordinate, value)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (Point<Index>, V)> + '_ {
+        self.points().map(|p| (p, self[p].clone()))
+    }
+}
+
+impl<V: Clone + fmt::Debug + HasEmpty> From<&DenseGrid<V>> for SparseGrid<V> {
+    fn from(g: &DenseGrid<V>) -> Self {
+        Self::from_dense(g, V::empty_value())
+    }
+}
+
+impl<V: Clone + std::fmt::Debug> std::ops::Index<Point<Index>> for SparseGrid<V> {
+    type Output = V;
+
+    fn index(&self, coordinate: Point<Index>) -> &Self::Output {
+        assert!(self.contains(coordinate), "{} out of bounds", coordinate);
+        self.cells.get(&coordinate).unwrap_or(&self.empty_value)
+    }
+}
+
+impl<V: Clone + std::fmt::Debug> std::ops::IndexMut<Point<Index>> for SparseGrid<V> {
+    fn index_mut(&mut self, coordinate: Point<Index>) -> &mut Self::Output {
+        self.include(coordinate);
+        self.cells
+            .entry(coordinate)
+            .or_insert_with(|| self.empty_value.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DenseGrid, Point, SparseGrid};
+    use crate::Grid;
+
+    #[test]
+    fn test_grows() {
+        let mut g = SparseGrid::new_with(0u8);
+        assert_eq!(g.size(), 0);
+        assert_eq!(g.get(Point::new(0, 0)), None);
+        g.set(Point::new(-5, 3), 1);
+        assert_eq!(g.size(), 1);
+        g.set(Point::new(5, -3), 2);
+        assert_eq!(g.width(), 11);
+        assert_eq!(g.height(), 7);
+        assert_eq!(g.len(), 2);
+        assert_eq!(g.get(Point::new(0, 0)), Some(0));
+        assert_eq!(g.get(Point::new(5, -3)), Some(2));
+        assert_eq!(g.get(Point::new(6, -3)), None);
+        assert_eq!(g.find(&1), Some(Point::new(-5, 3)));
+        assert_eq!(g.find(&0), Some(Point::new(-5, -3)));
+        assert_eq!(
+            g.iter().map(|(p, _)| p).take(2).collect::<Vec<_>>(),
+            vec![Point::new(-5, -3), Point::new(-4, -3)]
+        );
+        g[Point::new(10, 10)] += 3;
+        assert_eq!(g.max_x(), Some(10));
+        assert_eq!(g[Point::new(10, 10)], 3);
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let mut g = DenseGrid::new_with(Point::new(-1, -1), Point::new(2, 3), 0u8);
+        g.set(Point::new(0, 0), 1);
+        g.set(Point::new(2, 3), 2);
+        let sparse = SparseGrid::from(&g);
+        assert_eq!(sparse.width(), 4);
+        assert_eq!(sparse.height(), 5);
+        assert_eq!(sparse.to_dense(), Some(g));
+        assert_eq!(SparseGrid::<u8>::new().to_dense(), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        use crate::DijkstraMetric;
+        let g = SparseGrid::from_input("..#\n#.#\n#..", |c| c == '.');
+        let (res, preds) = g
+            .dijkstra(
+                Point::new(0, 0),
+                |g, p| g.get(p) == Some(true),
+                |_, _, _| 1usize,
+            )
+            .expect("should evaluate")
+            .into_parts();
+        assert_eq!(res.get(Point::new(2, 2)), Some(DijkstraMetric::Finite(4)));
+        assert_eq!(res.get(Point::new(2, 0)), Some(DijkstraMetric::Infinite));
+        assert_eq!(preds.get(Point::new(2, 2)), Some(Some(Point::new(1, 2))));
+    }
+}
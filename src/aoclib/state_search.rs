@@ -0,0 +1,204 @@
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use std::fmt;
+
+use super::point::Point;
+
+/// The result of a Dijkstra search over `(Point, S)` nodes, where `S` is whatever extra state
+/// the puzzle needs alongside position (facing, momentum, keys held, ...).
+#[derive(Debug, Clone)]
+pub struct StateSearch<S, MV>
+where
+    S: Ord + Clone + fmt::Debug,
+    MV: fmt::Debug + Clone + Copy,
+{
+    pub distances: BTreeMap<(Point, S), MV>,
+    pub predecessors: BTreeMap<(Point, S), Vec<(Point, S)>>,
+}
+
+impl<S, MV> StateSearch<S, MV>
+where
+    S: Ord + Clone + fmt::Debug,
+    MV: fmt::Debug
+        + Clone
+        + Copy
+        + PartialEq
+        + Eq
+        + PartialOrd
+        + Ord
+        + num_traits::Zero
+        + std::ops::Add,
+{
+    /// Run the search. `successors` is given each node as it's settled and yields the nodes
+    /// reachable from it along with the cost of getting there.
+    pub fn run<I, SF, SI>(starts: I, mut successors: SF) -> Self
+    where
+        I: IntoIterator<Item = (Point, S)>,
+        SF: FnMut(Point, &S) -> SI,
+        SI: IntoIterator<Item = (Point, S, MV)>,
+    {
+        use std::cmp::Reverse;
+
+        let mut distances = BTreeMap::new();
+        let mut predecessors: BTreeMap<(Point, S), Vec<(Point, S)>> = BTreeMap::new();
+        let mut visited = BTreeSet::new();
+        let mut unvisited = BinaryHeap::new();
+        for (point, state) in starts {
+            distances.insert((point, state.clone()), MV::zero());
+            unvisited.push(Reverse((MV::zero(), point, state)));
+        }
+        while let Some(Reverse((current, point, state))) = unvisited.pop() {
+            let node = (point, state);
+            if visited.contains(&node) {
+                continue;
+            }
+            for (next_point, next_state, cost) in successors(node.0, &node.1) {
+                let next_node = (next_point, next_state);
+                if visited.contains(&next_node) {
+                    continue;
+                }
+                let next = current + cost;
+                match distances.get(&next_node) {
+                    Some(v) if next > *v => continue,
+                    Some(v) if next == *v => {
+                        predecessors
+                            .entry(next_node)
+                            .or_default()
+                            .push(node.clone());
+                        continue;
+                    }
+                    _ => {}
+                }
+                distances.insert(next_node.clone(), next);
+                predecessors.insert(next_node.clone(), vec![node.clone()]);
+                unvisited.push(Reverse((next, next_node.0, next_node.1)));
+            }
+            visited.insert(node);
+        }
+        StateSearch {
+            distances,
+            predecessors,
+        }
+    }
+
+    pub fn distance_to(&self, point: Point, state: &S) -> Option<MV> {
+        self.distances.get(&(point, state.clone())).copied()
+    }
+
+    /// The cheapest distance to `point` in any state.
+    pub fn best_distance_to(&self, point: Point) -> Option<MV> {
+        self.distances
+            .iter()
+            .filter(|((p, _), _)| *p == point)
+            .map(|(_, d)| *d)
+            .min()
+    }
+
+    /// One shortest path to the given node, start first.
+    pub fn path_to(&self, point: Point, state: &S) -> Option<Vec<(Point, S)>> {
+        let mut node = (point, state.clone());
+        self.distances.get(&node)?;
+        let mut path = vec![node.clone()];
+        while let Some(pred) = self.predecessors.get(&node).and_then(|p| p.first()) {
+            node = pred.clone();
+            path.push(node.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every cell lying on some shortest path to the cheapest of the nodes matching `goal`.
+    pub fn cells_on_shortest_paths<GF>(&self, goal: GF) -> BTreeSet<Point>
+    where
+        GF: Fn(Point, &S) -> bool,
+    {
+        let mut seen = BTreeSet::new();
+        let Some(best) = self
+            .distances
+            .iter()
+            .filter(|((p, s), _)| goal(*p, s))
+            .map(|(_, d)| *d)
+            .min()
+        else {
+            return seen;
+        };
+        let mut work = self
+            .distances
+            .iter()
+            .filter(|((p, s), d)| goal(*p, s) && **d == best)
+            .map(|(n, _)| n.clone())
+            .collect::<Vec<_>>();
+        let mut seen_nodes = BTreeSet::new();
+        while let Some(node) = work.pop() {
+            if !seen_nodes.insert(node.clone()) {
+                continue;
+            }
+            seen.insert(node.0);
+            if let Some(preds) = self.predecessors.get(&node) {
+                work.extend(preds.iter().cloned());
+            }
+        }
+        seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DenseGrid, Grid, Point};
+
+    const MAZE: &str = "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    #[test]
+    fn test_turn_costs() {
+        let g = DenseGrid::from_input(MAZE, |c| c);
+        let start = g.find(&'S').unwrap();
+        let end = g.find(&'E').unwrap();
+        let res = g.search_states([(start, Point::new(1, 0))], |g, point, facing| {
+            let facing = *facing;
+            [
+                (point + facing, facing, 1usize),
+                (point, Point::new(facing.y, -facing.x), 1000),
+                (point, Point::new(-facing.y, facing.x), 1000),
+            ]
+            .into_iter()
+            .filter(|(p, _, _)| g.get(*p) != Some('#'))
+            .collect::<Vec<_>>()
+        });
+        assert_eq!(res.best_distance_to(end), Some(7036));
+        assert_eq!(res.cells_on_shortest_paths(|p, _| p == end).len(), 45);
+        let path = res.path_to(end, &Point::new(0, -1)).unwrap();
+        assert_eq!(path.first(), Some(&(start, Point::new(1, 0))));
+    }
+
+    #[test]
+    fn test_momentum() {
+        // may move at most two cells in the same direction before turning
+        let g = DenseGrid::new_with(Point::new(0, 0), Point::new(4, 1), ());
+        let res = g.search_states([(Point::new(0, 0), (Point::new(0, 0), 0))], |_, p, s| {
+            let (dir, run) = *s;
+            Point::new(0, 0)
+                .ordinal_neighbors_array()
+                .into_iter()
+                .filter(move |d| *d != Point::new(0, 0) - dir)
+                .filter(move |d| *d != dir || run < 2)
+                .map(move |d| {
+                    let run = if d == dir { run + 1 } else { 1 };
+                    (p + d, (d, run), 1usize)
+                })
+        });
+        assert_eq!(res.best_distance_to(Point::new(4, 0)), Some(6));
+    }
+}
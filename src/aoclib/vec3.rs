@@ -1,23 +1,214 @@
-use crate::dimval::DimVal;
+use crate::dimval::{inclusive_range, DimVal};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Hash, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Vec3<T: DimVal = i64> {
     pub x: T,
     pub y: T,
     pub z: T,
 }
-impl<I: DimVal + Eq> Eq for Vec3<I> {}
+impl<T: DimVal> Vec3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn as_array(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn dot(&self, other: Vec3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: Vec3<T>) -> Self {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn manhattan_norm(&self) -> T {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    pub fn chebyshev_norm(&self) -> T {
+        [self.y.abs(), self.z.abs()]
+            .into_iter()
+            .fold(self.x.abs(), |a, b| if b > a { b } else { a })
+    }
 
-impl<I: DimVal + Ord> Ord for Vec3<I> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+    /// The smaller of each pair of components.
+    pub fn component_min(&self, other: Vec3<T>) -> Self {
+        let pick = |a: T, b: T| if b < a { b } else { a };
+        Vec3::new(
+            pick(self.x, other.x),
+            pick(self.y, other.y),
+            pick(self.z, other.z),
+        )
+    }
+
+    /// The larger of each pair of components.
+    pub fn component_max(&self, other: Vec3<T>) -> Self {
+        let pick = |a: T, b: T| if b > a { b } else { a };
+        Vec3::new(
+            pick(self.x, other.x),
+            pick(self.y, other.y),
+            pick(self.z, other.z),
+        )
+    }
+
+    pub fn rotate(&self, rotation: &CubeRotation) -> Self {
+        rotation.apply(*self)
+    }
+
+    pub fn manhattan_distance_to(&self, other: Vec3<T>) -> T {
+        (*self - other).manhattan_norm()
+    }
+
+    pub fn chebyshev_distance_to(&self, other: Vec3<T>) -> T {
+        (*self - other).chebyshev_norm()
+    }
+
+    pub fn euclidean_distance_squared_to(&self, other: Vec3<T>) -> T {
+        let d = *self - other;
+        d.x * d.x + d.y * d.y + d.z * d.z
+    }
+
+    /// The surrounding cells (out of 26) that differ from this one in at most `max_axes`
+    /// coordinates.
+    fn neighbors_varying(&self, max_axes: usize) -> Vec<Self> {
+        let unit = inclusive_range(-T::one(), T::one()).collect::<Vec<_>>();
+        let mut res = vec![];
+        for dz in unit.iter() {
+            for dy in unit.iter() {
+                for dx in unit.iter() {
+                    let axes = [dx, dy, dz].iter().filter(|d| !d.is_zero()).count();
+                    if axes > 0 && axes <= max_axes {
+                        res.push(*self + Vec3::new(*dx, *dy, *dz));
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// The six cells sharing a face with this one.
+    pub fn neighbors6(&self) -> [Self; 6] {
+        self.neighbors_varying(1).try_into().unwrap()
+    }
+
+    /// The cells sharing a face or an edge with this one.
+    pub fn neighbors18(&self) -> [Self; 18] {
+        self.neighbors_varying(2).try_into().unwrap()
+    }
+
+    /// The cells sharing a face, an edge or a corner with this one.
+    pub fn neighbors26(&self) -> [Self; 26] {
+        self.neighbors_varying(3).try_into().unwrap()
+    }
+
+    /// Every point within Manhattan distance `radius` of this one (an octahedron), including
+    /// this one.
+    pub fn within_manhattan(&self, radius: T) -> impl Iterator<Item = Self> {
+        let center = *self;
+        inclusive_range(-radius, radius).flat_map(move |dz| {
+            let r = radius - dz.abs();
+            inclusive_range(-r, r).flat_map(move |dy| {
+                let r = r - dy.abs();
+                inclusive_range(-r, r).map(move |dx| center + Vec3::new(dx, dy, dz))
+            })
+        })
+    }
+
+    /// Every point within Chebyshev distance `radius` of this one (a cube), including this
+    /// one.
+    pub fn within_chebyshev(&self, radius: T) -> impl Iterator<Item = Self> {
+        let center = *self;
+        inclusive_range(-radius, radius).flat_map(move |dz| {
+            inclusive_range(-radius, radius).flat_map(move |dy| {
+                inclusive_range(-radius, radius).map(move |dx| center + Vec3::new(dx, dy, dz))
+            })
+        })
     }
 }
 
-impl<T: DimVal> Vec3<T> {
-    pub fn new(x: T, y: T, z: T) -> Self {
-        Self { x, y, z }
+/// One of the 24 rotations that map an axis-aligned cube onto itself (every way of pointing a
+/// die, ignoring reflections).
+///
+/// Component `i` of a rotated vector is component `axes[i]` of the original, negated if
+/// `negate[i]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CubeRotation {
+    axes: [usize; 3],
+    negate: [bool; 3],
+}
+
+impl CubeRotation {
+    pub const IDENTITY: CubeRotation = CubeRotation {
+        axes: [0, 1, 2],
+        negate: [false, false, false],
+    };
+
+    /// All 24 rotations, starting with the identity.
+    pub fn all() -> impl Iterator<Item = CubeRotation> {
+        const PERMUTATIONS: [([usize; 3], bool); 6] = [
+            ([0, 1, 2], false),
+            ([1, 2, 0], false),
+            ([2, 0, 1], false),
+            ([0, 2, 1], true),
+            ([1, 0, 2], true),
+            ([2, 1, 0], true),
+        ];
+        PERMUTATIONS.into_iter().flat_map(|(axes, odd)| {
+            (0..8u8).filter_map(move |signs| {
+                let negate = [signs & 1 != 0, signs & 2 != 0, signs & 4 != 0];
+                // proper rotations have determinant +1
+                let flips = negate.iter().filter(|n| **n).count() % 2 == 1;
+                (flips == odd).then_some(CubeRotation { axes, negate })
+            })
+        })
+    }
+
+    pub fn apply<T: DimVal>(&self, v: Vec3<T>) -> Vec3<T> {
+        let components = v.as_array();
+        let [x, y, z] = [0, 1, 2].map(|i| {
+            let c = components[self.axes[i]];
+            if self.negate[i] {
+                -c
+            } else {
+                c
+            }
+        });
+        Vec3::new(x, y, z)
+    }
+
+    /// The rotation equivalent to applying `first` and then `self`.
+    pub fn compose(&self, first: &CubeRotation) -> CubeRotation {
+        let axes = [0, 1, 2].map(|i| first.axes[self.axes[i]]);
+        let negate = [0, 1, 2].map(|i| self.negate[i] != first.negate[self.axes[i]]);
+        CubeRotation { axes, negate }
+    }
+
+    pub fn inverse(&self) -> CubeRotation {
+        let mut axes = [0; 3];
+        let mut negate = [false; 3];
+        for i in 0..3 {
+            axes[self.axes[i]] = i;
+            negate[self.axes[i]] = self.negate[i];
+        }
+        CubeRotation { axes, negate }
+    }
+}
+
+impl<T: DimVal + num_traits::Euclid> Vec3<T> {
+    /// Reduce each component into `0..modulus`, component-wise.
+    pub fn rem_euclid(&self, modulus: Vec3<T>) -> Self {
+        Vec3::new(
+            self.x.rem_euclid(&modulus.x),
+            self.y.rem_euclid(&modulus.y),
+            self.z.rem_euclid(&modulus.z),
+        )
     }
 }
 
@@ -33,11 +224,9 @@ impl<T: DimVal + std::ops::Add> std::ops::Add for Vec3<T> {
     }
 }
 
-impl<T: DimVal + std::ops::AddAssign> std::ops::AddAssign for Vec3<T> {
+impl<T: DimVal> std::ops::AddAssign for Vec3<T> {
     fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z += rhs.z
+        *self = *self + rhs;
     }
 }
 
@@ -53,10 +242,16 @@ impl<T: DimVal + std::ops::Sub> std::ops::Sub for Vec3<T> {
     }
 }
 
-impl<T: DimVal + std::ops::Mul<i64, Output = T>> std::ops::Mul<i64> for Vec3<T> {
+impl<T: DimVal> std::ops::SubAssign for Vec3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: DimVal> std::ops::Mul<T> for Vec3<T> {
     type Output = Vec3<T>;
 
-    fn mul(self, scalar: i64) -> Self::Output {
+    fn mul(self, scalar: T) -> Self::Output {
         Self::Output {
             x: self.x * scalar,
             y: self.y * scalar,
@@ -65,8 +260,172 @@ impl<T: DimVal + std::ops::Mul<i64, Output = T>> std::ops::Mul<i64> for Vec3<T>
     }
 }
 
-impl fmt::Display for Vec3 {
+impl<T: DimVal> std::ops::MulAssign<T> for Vec3<T> {
+    fn mul_assign(&mut self, scalar: T) {
+        *self = *self * scalar;
+    }
+}
+
+impl<T: DimVal> std::ops::Div<T> for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn div(self, scalar: T) -> Self::Output {
+        Self::Output {
+            x: self.x / scalar,
+            y: self.y / scalar,
+            z: self.z / scalar,
+        }
+    }
+}
+
+/// Truncating remainder of each component; see `rem_euclid` for the wrap-around kind.
+impl<T: DimVal> std::ops::Rem<T> for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn rem(self, scalar: T) -> Self::Output {
+        Self::Output {
+            x: self.x % scalar,
+            y: self.y % scalar,
+            z: self.z % scalar,
+        }
+    }
+}
+
+impl<T: DimVal> std::ops::Neg for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn neg(self) -> Self::Output {
+        Self::Output {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: DimVal> std::iter::Sum for Vec3<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Vec3::new(T::zero(), T::zero(), T::zero()), |a, b| a + b)
+    }
+}
+
+impl<'a, T: DimVal> std::iter::Sum<&'a Vec3<T>> for Vec3<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<T: DimVal> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl<T: DimVal> From<[T; 3]> for Vec3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl<T: DimVal> From<Vec3<T>> for (T, T, T) {
+    fn from(v: Vec3<T>) -> Self {
+        (v.x, v.y, v.z)
+    }
+}
+
+impl<T: DimVal> fmt::Display for Vec3<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "({}, {}, {})", self.x, self.y, self.z)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Vec3;
+
+    #[test]
+    fn test_operators() {
+        let mut v = Vec3::new(7, -3, 4);
+        assert_eq!(-v, Vec3::new(-7, 3, -4));
+        assert_eq!(v * 2, Vec3::new(14, -6, 8));
+        assert_eq!(v / 2, Vec3::new(3, -1, 2));
+        assert_eq!(v % 4, Vec3::new(3, -3, 0));
+        assert_eq!(v.rem_euclid(Vec3::new(5, 5, 5)), Vec3::new(2, 2, 4));
+        v -= Vec3::new(7, 0, 0);
+        assert_eq!(v, Vec3::new(0, -3, 4));
+        v *= -1;
+        assert_eq!(v, Vec3::new(0, 3, -4));
+        let total: Vec3 = [Vec3::new(1, 2, 3), Vec3::new(1, 1, 1)].iter().sum();
+        assert_eq!(total, Vec3::from((2, 3, 4)));
+        assert_eq!(Vec3::from([2, 3, 4]), total);
+        assert_eq!(Vec3::<i32>::new(1, 2, -3).to_string(), "(1, 2, -3)");
+    }
+
+    #[test]
+    fn test_products() {
+        let x = Vec3::new(1, 0, 0);
+        let y = Vec3::new(0, 1, 0);
+        assert_eq!(x.cross(y), Vec3::new(0, 0, 1));
+        assert_eq!(y.cross(x), Vec3::new(0, 0, -1));
+        assert_eq!(Vec3::new(1, 2, 3).dot(Vec3::new(4, -5, 6)), 12);
+        let v = Vec3::new(-3, 1, 2);
+        assert_eq!(v.manhattan_norm(), 6);
+        assert_eq!(v.chebyshev_norm(), 3);
+        assert_eq!(v.component_min(Vec3::new(0, 0, 5)), Vec3::new(-3, 0, 2));
+        assert_eq!(v.component_max(Vec3::new(0, 0, 5)), Vec3::new(0, 1, 5));
+    }
+
+    #[test]
+    fn test_cube_rotations() {
+        use super::CubeRotation;
+        use std::collections::BTreeSet;
+
+        let all = CubeRotation::all().collect::<Vec<_>>();
+        assert_eq!(all.len(), 24);
+        assert_eq!(all[0], CubeRotation::IDENTITY);
+        let v = Vec3::new(1, 2, 3);
+        let images = all.iter().map(|r| v.rotate(r)).collect::<BTreeSet<_>>();
+        assert_eq!(images.len(), 24);
+        let (x, y) = (Vec3::new(1, 0, 0), Vec3::new(0, 1, 0));
+        for r in all.iter() {
+            // rotations preserve handedness and lengths
+            assert_eq!(r.apply(x).cross(r.apply(y)), r.apply(x.cross(y)));
+            assert_eq!(r.inverse().apply(r.apply(v)), v);
+            for s in all.iter() {
+                assert_eq!(s.compose(r).apply(v), s.apply(r.apply(v)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Vec3::new(1, 2, 3);
+        let b = Vec3::new(-1, 4, 7);
+        assert_eq!(a.manhattan_distance_to(b), 8);
+        assert_eq!(a.chebyshev_distance_to(b), 4);
+        assert_eq!(a.euclidean_distance_squared_to(b), 24);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let v = Vec3::new(0, 0, 0);
+        assert!(v
+            .neighbors6()
+            .iter()
+            .all(|n| v.manhattan_distance_to(*n) == 1));
+        assert!(v
+            .neighbors18()
+            .iter()
+            .all(|n| v.manhattan_distance_to(*n) <= 2));
+        assert!(v
+            .neighbors26()
+            .iter()
+            .all(|n| v.chebyshev_distance_to(*n) == 1));
+        assert_eq!(v.within_manhattan(1).count(), 7);
+        assert_eq!(v.within_manhattan(2).count(), 25);
+        assert_eq!(v.within_chebyshev(1).count(), 27);
+        assert!(v
+            .within_manhattan(3)
+            .all(|n| v.manhattan_distance_to(n) <= 3));
+    }
+}
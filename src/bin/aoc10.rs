@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 use std::io::Read;
 
-use aoclib::{DenseGrid, Point};
+use aoclib::{DenseGrid, Grid, Point};
 use petgraph::algo::floyd_warshall;
 use petgraph::graph::{DiGraph, NodeIndex};
 
@@ -27,34 +27,22 @@ impl Map {
                     .ok_or(anyhow::anyhow!("failed to parse {} as a digit", f))
             }
         })?;
-        let mut graph = DiGraph::new();
-        let mut node_indexes = BTreeMap::new();
-        let mut starts = vec![];
-        let mut ends = vec![];
-        for (point, value) in grid.iter() {
-            let (north, east, south, west) = point.ordinal_neighbors();
-            let index = node_indexes
-                .entry(point)
-                .or_insert_with(|| graph.add_node(point))
-                .to_owned();
-            if value == 0 {
-                starts.push(point);
-            }
-            if value == 9 {
-                ends.push(point);
-            }
-            for neighbor in &[north, east, south, west] {
-                if let Some(neighbor_val) = grid.get(*neighbor) {
-                    if neighbor_val == value + 1 {
-                        let neighbor_index = node_indexes
-                            .entry(*neighbor)
-                            .or_insert_with(|| graph.add_node(*neighbor))
-                            .to_owned();
-                        graph.add_edge(index, neighbor_index, ());
-                    }
-                }
-            }
-        }
+        let (graph, node_indexes) = grid
+            .to_graph(|g, point, neighbor| match (g.get(point), g.get(neighbor)) {
+                (Some(value), Some(neighbor_val)) => neighbor_val == value + 1,
+                _ => false,
+            })
+            .into_parts();
+        let starts = grid
+            .iter()
+            .filter(|(_, v)| **v == 0)
+            .map(|(p, _)| p)
+            .collect();
+        let ends = grid
+            .iter()
+            .filter(|(_, v)| **v == 9)
+            .map(|(p, _)| p)
+            .collect();
         Ok(Map {
             grid,
             graph,
@@ -101,7 +89,7 @@ impl Map {
             .grid
             .iter()
             .filter(|(p, v)| {
-                if *v == 9 {
+                if **v == 9 {
                     true
                 } else {
                     let index = self.node_indexes.get(p).unwrap();
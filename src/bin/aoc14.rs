@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 use std::io::Read;
 
-use aoclib::{DenseGrid, Point};
+use aoclib::{DenseGrid, Grid, Point, Rect};
 use itertools::Itertools;
 use nom::bytes::complete::tag;
 use nom::character::complete::{multispace1, newline};
@@ -41,10 +41,8 @@ impl Robot {
     }
 
     fn advance(&mut self, steps: u32, width: i64, height: i64) {
-        self.position = Point::new(
-            (self.position.x + self.velocity.x * steps as i64).rem_euclid(width),
-            (self.position.y + self.velocity.y * steps as i64).rem_euclid(height),
-        )
+        self.position =
+            (self.position + self.velocity * steps as i64).rem_euclid(Point::new(width, height))
     }
 }
 
@@ -63,6 +61,16 @@ enum Quadrant {
     BottomRight,
 }
 
+impl Quadrant {
+    /// In the same order as `Rect::quadrants`.
+    const ALL: [Quadrant; 4] = [
+        Quadrant::TopLeft,
+        Quadrant::TopRight,
+        Quadrant::BottomLeft,
+        Quadrant::BottomRight,
+    ];
+}
+
 impl Problem {
     fn parse(s: &str) -> IResult<&str, Self> {
         map(
@@ -91,24 +99,20 @@ impl Problem {
     }
 
     fn count_by_quadrant(&self) -> usize {
+        let floor = Rect::with_size(Point::new(0, 0), self.width as usize, self.height as usize)
+            .expect("the floor has some area");
         let mut robots_by_quadrant = BTreeMap::new();
-        let vdiv = self.width / 2;
-        let hdiv = self.height / 2;
-        for robot in &self.robots {
-            let quadrant = match (
-                robot.position.x < vdiv,
-                robot.position.x > vdiv,
-                robot.position.y < hdiv,
-                robot.position.y > hdiv,
-            ) {
-                (true, false, true, false) => Some(Quadrant::TopLeft),
-                (false, true, true, false) => Some(Quadrant::TopRight),
-                (true, false, false, true) => Some(Quadrant::BottomLeft),
-                (false, true, false, true) => Some(Quadrant::BottomRight),
-                _ => None,
+        for (quadrant, rect) in Quadrant::ALL.into_iter().zip(floor.quadrants()) {
+            let Some(rect) = rect else {
+                continue;
             };
-            if let Some(quadrant) = quadrant {
-                *robots_by_quadrant.entry(quadrant).or_insert(0) += 1
+            let count = self
+                .robots
+                .iter()
+                .filter(|robot| rect.contains(robot.position))
+                .count();
+            if count > 0 {
+                robots_by_quadrant.insert(quadrant, count);
             }
         }
         println!("{:?}", robots_by_quadrant);
@@ -138,7 +142,7 @@ impl Problem {
 
     fn debug_image(&self, s: String) {
         let grid = self.debug_grid();
-        let max_count = grid.iter().map(|(_, v)| v).max().unwrap() as f32;
+        let max_count = grid.iter().map(|(_, v)| *v).max().unwrap() as f32;
         grid.save_to_image(
             |f| {
                 if *f == 0 {
@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
 use std::io::Read;
 
-use aoclib::{DenseGrid, Point};
+use aoclib::{CellChar, DenseGrid, Direction, Point};
 use itertools::Itertools;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
@@ -20,28 +20,25 @@ impl aoclib::HasEmpty for Cell {
     }
 }
 
-impl Cell {
-    fn from_char(c: char) -> Self {
-        match c {
-            '#' => Cell::Wall,
-            'O' => Cell::Box,
-            '@' => Cell::Robot,
-            '.' => Cell::Empty,
-            '[' => Cell::BoxLeft,
-            ']' => Cell::BoxRight,
-            _ => panic!("unhandled input {}", c),
-        }
+aoclib::cell_chars! {
+    Cell {
+        Robot => '@',
+        Box => 'O',
+        Wall => '#',
+        Empty => '.',
+        BoxLeft => '[',
+        BoxRight => ']',
     }
 }
 
 fn parse_instruction(c: char) -> Option<Point> {
     match c {
-        '^' => Some(Point::new(0, -1)),
-        'v' => Some(Point::new(0, 1)),
-        '<' => Some(Point::new(-1, 0)),
-        '>' => Some(Point::new(1, 0)),
         '\n' => None,
-        _ => panic!("unhandled instruction '{}", c),
+        _ => Some(
+            Direction::from_arrow(c)
+                .unwrap_or_else(|| panic!("unhandled instruction '{}", c))
+                .as_vector(),
+        ),
     }
 }
 
@@ -58,15 +55,15 @@ impl Problem {
         let mut s = String::new();
         stdin.read_to_string(&mut s)?;
         let (raw_map, raw_instructions) = s.split_once("\n\n").unwrap();
-        let map = DenseGrid::from_input(raw_map, Cell::from_char);
+        let parsed = DenseGrid::parse_with(raw_map, &['@'], Cell::from_char)?;
         let instructions = raw_instructions
             .trim()
             .chars()
             .filter_map(parse_instruction)
             .collect();
-        let robot = map.find(&Cell::Robot).unwrap();
+        let robot = parsed.marker('@')?;
         Ok(Problem {
-            map,
+            map: parsed.grid,
             instructions,
             robot,
         })
@@ -138,14 +135,7 @@ impl Problem {
     }
 
     fn debug(&self) {
-        self.map.dump_with(|f| match f {
-            Cell::Wall => '#',
-            Cell::Empty => '.',
-            Cell::Box => 'O',
-            Cell::Robot => '@',
-            Cell::BoxLeft => '[',
-            Cell::BoxRight => ']',
-        })
+        println!("{}", self.map);
     }
 
     fn simulate(mut self, debug_each: bool) -> i64 {
@@ -171,7 +161,7 @@ impl Problem {
             }
             if let Some(steps) = self.can_move_to(instruction) {
                 self.do_a_move(steps, instruction);
-                self.robot = self.robot + instruction;
+                self.robot += instruction;
             }
             if debug_each {
                 self.debug();
@@ -185,7 +175,7 @@ impl Problem {
             Point::new(0, 0),
             Point::new(self.map.width() as i64 * 2, self.map.height() as i64),
         );
-        for (p, v) in self.map.iter() {
+        for (p, &v) in self.map.iter() {
             let p1 = Point::new(p.x * 2, p.y);
             let p2 = Point::new(p.x * 2 + 1, p.y);
             let c1 = match v {
@@ -200,7 +190,7 @@ impl Problem {
             new_map.set(p1, c1);
             new_map.set(p2, c2);
         }
-        let robot = new_map.find(&Cell::Robot).unwrap();
+        let robot = Point::new(self.robot.x * 2, self.robot.y);
         Problem {
             robot,
             map: new_map,
@@ -1,4 +1,4 @@
-use aoclib::{DenseGrid, Point};
+use aoclib::{DenseGrid, Grid, Point};
 use clap::Parser;
 use nom::bytes::complete::tag;
 use nom::character::complete::newline;
@@ -6,9 +6,8 @@ use nom::combinator::map;
 use nom::multi::separated_list1;
 use nom::sequence::separated_pair;
 use nom::IResult;
-use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::BTreeSet;
 use std::io::Read;
 
 #[derive(Debug, Parser)]
@@ -54,54 +53,34 @@ impl Problem {
         Ok(Problem { map, points })
     }
 
-    fn build_graph(
-        &self,
-    ) -> (
-        BTreeMap<Point, NodeIndex>,
-        petgraph::graph::DiGraph<Point, ()>,
-    ) {
-        let mut graph = petgraph::graph::DiGraph::new();
-        let mut nodes = BTreeMap::new();
-        for (point, value) in self.map.iter() {
-            if value {
-                continue;
-            }
-            let node = *nodes.entry(point).or_insert_with(|| graph.add_node(point));
-            for neighbor in point.ordinal_neighbors_array() {
-                if self.map.get(neighbor) != Some(false) {
-                    continue;
-                }
-                let nnode = *nodes
-                    .entry(neighbor)
-                    .or_insert_with(|| graph.add_node(neighbor));
-                graph.add_edge(node, nnode, ());
-            }
-        }
-        (nodes, graph)
-    }
-
     fn part1(&mut self, steps: usize) {
         for point in self.points.iter().take(steps) {
             self.map.set(*point, true);
         }
-        let (mut nodes, mut graph) = self.build_graph();
-        let start = *nodes
-            .entry(Point::new(0, 0))
-            .or_insert_with(|| graph.add_node(Point::new(0, 0)));
-        let exit_point = Point::new(self.map.max_x, self.map.max_y);
-        let goal = *nodes
-            .entry(exit_point)
-            .or_insert_with(|| graph.add_node(exit_point));
-        let d = petgraph::algo::dijkstra(&graph, start, Some(goal), |_| 1);
-        println!("part 1: {}", d.get(&goal).unwrap());
+        let exit_point = self.map.rect().max;
+        let (cost, _) = self
+            .map
+            .astar_to(
+                Point::new(0, 0),
+                exit_point,
+                |g, p| g.get(p) == Some(false),
+                |_, _, _| 1usize,
+            )
+            .unwrap();
+        println!("part 1: {}", cost);
     }
 
     fn part2(&mut self) {
         // this feels like it's probably Ford-Fulkerson, but whatever
         // just do it the brute-force way.
-        let (nodes, graph) = self.build_graph();
+        let (graph, nodes) = self
+            .map
+            .to_graph(|g, point, neighbor| {
+                g.get(point) == Some(false) && g.get(neighbor) == Some(false)
+            })
+            .into_parts();
         let start = *nodes.get(&Point::new(0, 0)).unwrap();
-        let exit_point = Point::new(self.map.max_x, self.map.max_y);
+        let exit_point = self.map.rect().max;
         let goal = *nodes.get(&exit_point).unwrap();
         let mut removed = BTreeSet::new();
         for (i, point) in self.points.iter().enumerate() {
@@ -1,5 +1,4 @@
-use aoclib::DijkstraMetric;
-use aoclib::{DenseGrid, Point};
+use aoclib::{CellChar, DenseGrid, Grid, Point};
 use std::collections::{BTreeMap, BTreeSet};
 use std::io::Read;
 
@@ -11,23 +10,16 @@ enum Cell {
     End,
 }
 
-impl aoclib::HasEmpty for Cell {
-    fn empty_value() -> Self {
-        Self::Empty
+aoclib::cell_chars! {
+    Cell {
+        Wall => '#',
+        Empty => '.',
+        Start => 'S',
+        End => 'E',
     }
 }
 
 impl Cell {
-    fn parse(c: char) -> Self {
-        match c {
-            '#' => Cell::Wall,
-            '.' => Cell::Empty,
-            'S' => Cell::Start,
-            'E' => Cell::End,
-            _ => panic!("unhandled input {}", c),
-        }
-    }
-
     fn traversible(&self) -> bool {
         match self {
             Self::Wall => false,
@@ -39,6 +31,7 @@ impl Cell {
 #[derive(Debug, Clone)]
 struct Problem {
     map: DenseGrid<Cell>,
+    end: Point,
 }
 
 impl Problem {
@@ -46,14 +39,17 @@ impl Problem {
         let mut stdin = std::io::stdin().lock();
         let mut s = String::new();
         stdin.read_to_string(&mut s)?;
-        let map = DenseGrid::from_input(&s, Cell::parse);
-        Ok(Problem { map })
+        let parsed = DenseGrid::parse_with(&s, &['E'], Cell::from_char)?;
+        Ok(Problem {
+            end: parsed.marker('E')?,
+            map: parsed.grid,
+        })
     }
 
     fn solve(&self, max_shortcut_len: usize, threshold: usize) -> anyhow::Result<usize> {
-        let end = self.map.find(&Cell::End).unwrap();
+        let end = self.end;
 
-        let (dmap, _) = self.map.dijkstra(
+        let paths = self.map.dijkstra(
             end,
             |g, p| g.get(p).map(|c| c.traversible()).unwrap_or(false),
             |_, _, _| 1,
@@ -65,39 +61,33 @@ impl Problem {
         // lowest value with manhattan distance 20
         let mut shortcuts = BTreeMap::new();
         for (point, value) in self.map.iter() {
-            if value == Cell::Wall {
+            if *value == Cell::Wall {
                 continue;
             }
-            let Some(DijkstraMetric::Finite(my_time)) = dmap.get(point) else {
+            let Some(my_time) = paths.distance_to(point) else {
                 continue;
             };
-            for xdelta in (-1 * msl)..=msl {
-                for ydelta in (-1 * msl)..=msl {
-                    let other = point + Point::new(xdelta, ydelta);
-                    let distance = point.manhattan_distance_to(other);
-                    if distance > max_shortcut_len {
-                        continue;
-                    }
-                    match self.map.get(other) {
-                        Some(Cell::Wall) | None => continue,
-                        _ => {}
-                    }
-                    let Some(DijkstraMetric::Finite(their_time)) = dmap.get(other) else {
-                        continue;
-                    };
-                    if their_time > my_time {
-                        continue;
-                    }
-                    if their_time + distance > my_time {
-                        continue;
-                    }
-                    let saved = my_time - their_time - distance;
-                    if saved >= threshold {
-                        shortcuts
-                            .entry(saved)
-                            .or_insert_with(BTreeSet::new)
-                            .insert((point, other));
-                    }
+            for other in point.within_manhattan(msl) {
+                let distance = point.manhattan_steps_to(other);
+                match self.map.get(other) {
+                    Some(Cell::Wall) | None => continue,
+                    _ => {}
+                }
+                let Some(their_time) = paths.distance_to(other) else {
+                    continue;
+                };
+                if their_time > my_time {
+                    continue;
+                }
+                if their_time + distance > my_time {
+                    continue;
+                }
+                let saved = my_time - their_time - distance;
+                if saved >= threshold {
+                    shortcuts
+                        .entry(saved)
+                        .or_insert_with(BTreeSet::new)
+                        .insert((point, other));
                 }
             }
         }
@@ -3,6 +3,7 @@ use std::io::Read;
 use std::sync::OnceLock;
 
 use aoclib::petgraph_bellman_ford_multi::bellman_ford_multi_predecessors;
+use aoclib::Direction;
 use itertools::Itertools;
 use memoize::memoize;
 use petgraph::graph::{DiGraph, NodeIndex};
@@ -179,27 +180,10 @@ impl Code {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 enum DPadButton {
-    Up,
-    Down,
-    Left,
-    Right,
+    Move(Direction),
     A,
 }
 
-impl DPadButton {
-    fn mirror(&self) -> Self {
-        use DPadButton::*;
-
-        match self {
-            Up => Down,
-            Down => Up,
-            Left => Right,
-            Right => Left,
-            A => panic!("nope"),
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
 struct NumPad {
     paths: BTreeMap<(CodeButton, CodeButton), Vec<Vec<DPadButton>>>,
@@ -210,18 +194,18 @@ fn add_edges(
     nodes: &BTreeMap<CodeButton, NodeIndex>,
     from_c: CodeButton,
     to_c: CodeButton,
-    dir: DPadButton,
+    dir: Direction,
 ) {
     let source_node = nodes[&from_c];
     let dest_node = nodes[&to_c];
-    graph.add_edge(source_node, dest_node, dir);
-    graph.add_edge(dest_node, source_node, dir.mirror());
+    graph.add_edge(source_node, dest_node, DPadButton::Move(dir));
+    graph.add_edge(dest_node, source_node, DPadButton::Move(dir.reverse()));
 }
 
 impl NumPad {
     fn new() -> Self {
         use CodeButton::*;
-        use DPadButton::{Down, Right};
+        use Direction::{East, South};
         let mut graph = DiGraph::new();
         let mut nodes = BTreeMap::<CodeButton, NodeIndex>::new();
         for cb in [
@@ -229,21 +213,21 @@ impl NumPad {
         ] {
             nodes.entry(cb).or_insert_with(|| graph.add_node(cb));
         }
-        add_edges(&mut graph, &nodes, Seven, Eight, Right);
-        add_edges(&mut graph, &nodes, Eight, Nine, Right);
-        add_edges(&mut graph, &nodes, Four, Five, Right);
-        add_edges(&mut graph, &nodes, Five, Six, Right);
-        add_edges(&mut graph, &nodes, One, Two, Right);
-        add_edges(&mut graph, &nodes, Two, Three, Right);
-        add_edges(&mut graph, &nodes, Zero, A, Right);
-        add_edges(&mut graph, &nodes, Seven, Four, Down);
-        add_edges(&mut graph, &nodes, Four, One, Down);
-        add_edges(&mut graph, &nodes, Eight, Five, Down);
-        add_edges(&mut graph, &nodes, Five, Two, Down);
-        add_edges(&mut graph, &nodes, Two, Zero, Down);
-        add_edges(&mut graph, &nodes, Nine, Six, Down);
-        add_edges(&mut graph, &nodes, Six, Three, Down);
-        add_edges(&mut graph, &nodes, Three, A, Down);
+        add_edges(&mut graph, &nodes, Seven, Eight, East);
+        add_edges(&mut graph, &nodes, Eight, Nine, East);
+        add_edges(&mut graph, &nodes, Four, Five, East);
+        add_edges(&mut graph, &nodes, Five, Six, East);
+        add_edges(&mut graph, &nodes, One, Two, East);
+        add_edges(&mut graph, &nodes, Two, Three, East);
+        add_edges(&mut graph, &nodes, Zero, A, East);
+        add_edges(&mut graph, &nodes, Seven, Four, South);
+        add_edges(&mut graph, &nodes, Four, One, South);
+        add_edges(&mut graph, &nodes, Eight, Five, South);
+        add_edges(&mut graph, &nodes, Five, Two, South);
+        add_edges(&mut graph, &nodes, Two, Zero, South);
+        add_edges(&mut graph, &nodes, Nine, Six, South);
+        add_edges(&mut graph, &nodes, Six, Three, South);
+        add_edges(&mut graph, &nodes, Three, A, South);
         let paths = find_all_shortest_paths(&graph);
         Self { paths }
     }
@@ -256,20 +240,21 @@ struct DPads {
 
 impl DPads {
     fn new() -> Self {
-        use DPadButton::*;
+        use DPadButton::{Move, A};
+        use Direction::{East, North, South, West};
         let mut graph = DiGraph::new();
 
         let mut nodes: BTreeMap<DPadButton, NodeIndex> = BTreeMap::new();
 
-        for key in [Left, Down, Up, Right, A] {
+        for key in [Move(West), Move(South), Move(North), Move(East), A] {
             nodes.insert(key, graph.add_node(key));
         }
 
-        Self::add_dpad_edge(&mut graph, &nodes, Left, Down, Right);
-        Self::add_dpad_edge(&mut graph, &nodes, Down, Right, Right);
-        Self::add_dpad_edge(&mut graph, &nodes, Up, A, Right);
-        Self::add_dpad_edge(&mut graph, &nodes, Up, Down, Down);
-        Self::add_dpad_edge(&mut graph, &nodes, A, Right, Down);
+        Self::add_dpad_edge(&mut graph, &nodes, Move(West), Move(South), East);
+        Self::add_dpad_edge(&mut graph, &nodes, Move(South), Move(East), East);
+        Self::add_dpad_edge(&mut graph, &nodes, Move(North), A, East);
+        Self::add_dpad_edge(&mut graph, &nodes, Move(North), Move(South), South);
+        Self::add_dpad_edge(&mut graph, &nodes, A, Move(East), South);
 
         let paths = find_all_shortest_paths(&graph);
 
@@ -281,12 +266,12 @@ impl DPads {
         nodes: &BTreeMap<DPadButton, NodeIndex>,
         source: DPadButton,
         dest: DPadButton,
-        direction: DPadButton,
+        direction: Direction,
     ) {
         let snode = nodes[&source];
         let dnode = nodes[&dest];
-        graph.add_edge(snode, dnode, direction);
-        graph.add_edge(dnode, snode, direction.mirror());
+        graph.add_edge(snode, dnode, DPadButton::Move(direction));
+        graph.add_edge(dnode, snode, DPadButton::Move(direction.reverse()));
     }
 }
 
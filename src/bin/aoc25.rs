@@ -1,6 +1,6 @@
 use std::io::Read;
 
-use aoclib::{DenseGrid, HasEmpty};
+use aoclib::DenseGrid;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum Cell {
@@ -8,41 +8,18 @@ enum Cell {
     Filled,
 }
 
-impl HasEmpty for Cell {
-    fn empty_value() -> Self {
-        Cell::Empty
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Schematic {
-    grid: DenseGrid<Cell>,
-    columns: Vec<usize>,
-}
-
-impl Schematic {
-    fn new(grid: DenseGrid<Cell>, is_lock: bool) -> Self {
-        let columns = grid
-            .columns()
-            .map(|c| {
-                if is_lock {
-                    c.iter().take_while(|i| matches!(i, Cell::Filled)).count()
-                } else {
-                    c.iter()
-                        .rev()
-                        .take_while(|i| matches!(i, Cell::Filled))
-                        .count()
-                }
-            })
-            .collect();
-        Schematic { grid, columns }
+aoclib::cell_chars! {
+    Cell {
+        Empty => '.',
+        Filled => '#',
     }
 }
 
 #[derive(Debug)]
 struct Problem {
-    keys: Vec<Schematic>,
-    locks: Vec<Schematic>,
+    /// Each key's empty space: Filled wherever the key isn't.
+    key_gaps: Vec<DenseGrid<Cell>>,
+    locks: Vec<DenseGrid<Cell>>,
 }
 
 impl Problem {
@@ -50,36 +27,33 @@ impl Problem {
         let mut stdin = std::io::stdin().lock();
         let mut s = String::new();
         stdin.read_to_string(&mut s)?;
-        let mut keys = Vec::new();
+        let mut key_gaps = Vec::new();
         let mut locks = Vec::new();
         for item in s.split("\n\n") {
-            let g = DenseGrid::from_input(item, |c| match c {
-                '#' => Cell::Filled,
-                '.' => Cell::Empty,
-                _ => {
-                    panic!("unhandled input {}", c)
-                }
-            });
-            let is_lock = g.rows().next().unwrap().iter().all(|c| *c == Cell::Filled);
-            let schematic = Schematic::new(g, is_lock);
+            let mut g = DenseGrid::<Cell>::parse(item)?;
+            let is_lock = g
+                .row(0)
+                .is_some_and(|r| r.iter().all(|c| *c == Cell::Filled));
             if is_lock {
-                locks.push(schematic);
+                locks.push(g);
             } else {
-                keys.push(schematic);
+                for (_, cell) in g.iter_mut() {
+                    *cell = match cell {
+                        Cell::Empty => Cell::Filled,
+                        Cell::Filled => Cell::Empty,
+                    };
+                }
+                key_gaps.push(g);
             }
         }
-        Ok(Problem { keys, locks })
+        Ok(Problem { key_gaps, locks })
     }
 
     fn part1(&self) -> usize {
-        itertools::iproduct!(self.locks.iter(), self.keys.iter())
-            .filter(|(lock, key)| {
-                let height = lock.grid.height();
-                lock.columns
-                    .iter()
-                    .zip(key.columns.iter())
-                    .all(|(lc, kc)| lc + kc <= height)
-            })
+        // a lock fits a key if every pin lands in the key's empty space; the lock's own empty
+        // cells don't care what's under them
+        itertools::iproduct!(self.locks.iter(), self.key_gaps.iter())
+            .filter(|(lock, gaps)| !gaps.find_pattern(lock, Some(&Cell::Empty)).is_empty())
             .count()
     }
 }
@@ -1,202 +1,166 @@
-use aoclib::Point;
-use std::cmp::max;
+use aoclib::{DenseGrid, Direction, Grid, Point};
 use std::collections::BTreeSet;
 use std::io::Read;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn rotate_halfpi_clockwise(&self) -> Self {
-        match self {
-            Self::Up => Self::Right,
-            Self::Right => Self::Down,
-            Self::Down => Self::Left,
-            Self::Left => Self::Up,
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 struct Guard {
     position: Point,
     direction: Direction,
 }
 
-impl Guard {
-    fn move_to(&mut self, end_position: Point) -> Vec<Self> {
-        let mut res = vec![];
-        match self.direction {
-            Direction::Up => {
-                assert!(self.position.x == end_position.x);
-                assert!(self.position.y >= end_position.y);
-            }
-            Direction::Down => {
-                assert!(self.position.x == end_position.x);
-                assert!(self.position.y <= end_position.y);
-            }
-            Direction::Left => {
-                assert!(self.position.y == end_position.y);
-                assert!(self.position.x >= end_position.x);
-            }
-            Direction::Right => {
-                assert!(self.position.y == end_position.y);
-                assert!(self.position.x <= end_position.x);
-            }
-        }
-        while self.position != end_position {
-            res.push(self.clone());
-            match self.direction {
-                Direction::Up => self.position.y -= 1,
-                Direction::Down => self.position.y += 1,
-                Direction::Left => self.position.x -= 1,
-                Direction::Right => self.position.x += 1,
-            }
-        }
-        res.push(self.clone());
-        self.direction = self.direction.rotate_halfpi_clockwise();
-        res
-    }
-}
-
 #[derive(Debug)]
 struct Map {
-    obstructions: Vec<Point>,
+    obstructions: DenseGrid<bool>,
     guard: Guard,
-    max_x: i64,
-    max_y: i64,
+    /// For every (cell, direction) state, the state the guard is in after walking up to the
+    /// next obstruction and turning, or None if they walk off the map instead. Indexed by
+    /// `state_index`.
+    jumps: Vec<Option<Guard>>,
 }
 
 impl Map {
-    fn from_str(s: &str) -> Self {
-        let mut max_x = 0;
-        let mut max_y = 0;
-        let mut guard = None;
-        let mut obstructions = vec![];
-        for (y, line) in s.lines().enumerate() {
-            max_y = max(max_y, y as i64);
-            for (x, cell) in line.chars().enumerate() {
-                max_x = max(max_x, x as i64);
-                let this = Point::new(x as i64, y as i64);
-                if cell == '#' {
-                    obstructions.push(this);
-                } else if cell == '^' {
-                    guard = Some(Guard {
-                        position: this,
-                        direction: Direction::Up,
-                    })
-                }
-            }
-        }
-        Map {
-            max_x,
-            max_y,
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let cells = DenseGrid::from_input(s.trim(), |c| c);
+        let position = cells
+            .find(&'^')
+            .ok_or_else(|| anyhow::anyhow!("no guard on the map"))?;
+        let obstructions = DenseGrid::from_input(s.trim(), |c| c == '#');
+        let index = obstructions.blocker_index(|g, p| g.get(p) == Some(true));
+        let mut map = Map {
+            jumps: vec![None; obstructions.size() * Direction::ALL.len()],
             obstructions,
-            guard: guard.unwrap(),
+            guard: Guard {
+                position,
+                direction: Direction::North,
+            },
+        };
+        for (position, &blocked) in map.obstructions.iter() {
+            if blocked {
+                continue;
+            }
+            for direction in Direction::ALL {
+                let step = direction.as_vector();
+                let state = map.state_index(Guard {
+                    position,
+                    direction,
+                });
+                map.jumps[state] = index.next_blocker(position, step).map(|hit| Guard {
+                    position: hit - step,
+                    direction: direction.turn_cw(),
+                });
+            }
         }
+        Ok(map)
     }
 
-    fn find_next_obstruction(&self, position: Point, direction: Direction) -> (Point, bool) {
-        let end = match direction {
-            Direction::Up => self
-                .obstructions
-                .iter()
-                .filter(|p| p.x == position.x && p.y <= position.y)
-                .max_by_key(|p| p.y),
-            Direction::Left => self
-                .obstructions
-                .iter()
-                .filter(|p| p.y == position.y && p.x <= position.x)
-                .max_by_key(|p| p.x),
-            Direction::Down => self
-                .obstructions
-                .iter()
-                .filter(|p| p.x == position.x && p.y >= position.y)
-                .min_by_key(|p| p.y),
-            Direction::Right => self
-                .obstructions
-                .iter()
-                .filter(|p| p.y == position.y && p.x >= position.x)
-                .min_by_key(|p| p.x),
-        };
-        if let Some(end) = end {
-            let pt = match direction {
-                Direction::Up => Point::new(end.x, end.y + 1),
-                Direction::Down => Point::new(end.x, end.y - 1),
-                Direction::Left => Point::new(end.x + 1, end.y),
-                Direction::Right => Point::new(end.x - 1, end.y),
-            };
-            (pt, false)
-        } else {
-            let pt = match direction {
-                Direction::Up => Point::new(position.x, 0),
-                Direction::Down => Point::new(position.x, self.max_y),
-                Direction::Left => Point::new(0, position.y),
-                Direction::Right => Point::new(self.max_x, position.y),
+    fn state_index(&self, guard: Guard) -> usize {
+        let offset = guard.position - self.obstructions.origin();
+        let cell = offset.y as usize * self.obstructions.width() + offset.x as usize;
+        cell * Direction::ALL.len() + guard.direction as usize
+    }
+
+    /// Add an obstruction at `at` by redirecting every state whose walk would now end there.
+    /// Those all lie on the four lines running back from `at` to the previous obstruction, so
+    /// this is O(width + height). Returns the overwritten entries, for `unpatch`.
+    fn patch(&mut self, at: Point) -> Vec<(usize, Option<Guard>)> {
+        let mut undo = vec![];
+        for direction in Direction::ALL {
+            let step = direction.as_vector();
+            let stop = Guard {
+                position: at - step,
+                direction: direction.turn_cw(),
             };
-            (pt, true)
+            let behind = self
+                .obstructions
+                .ray_iter(at, direction.reverse().as_vector())
+                .take_while(|p| self.obstructions.get(*p) == Some(false))
+                .collect::<Vec<_>>();
+            for position in behind {
+                let state = self.state_index(Guard {
+                    position,
+                    direction,
+                });
+                undo.push((state, self.jumps[state]));
+                self.jumps[state] = Some(stop);
+            }
         }
+        undo
     }
 
-    fn get_path_and_loop(&self) -> (BTreeSet<Point>, bool) {
-        let mut visited = BTreeSet::new();
-        let mut visited_guards = BTreeSet::new();
-        let mut guard = self.guard.clone();
-        visited_guards.insert(self.guard.clone());
+    fn unpatch(&mut self, undo: Vec<(usize, Option<Guard>)>) {
+        for (state, jump) in undo.into_iter().rev() {
+            self.jumps[state] = jump;
+        }
+    }
+
+    /// Follow the jump table from the guard's starting state and report whether it loops.
+    ///
+    /// `seen` holds, for each state, the `stamp` of the last run that passed through it, so
+    /// the same buffer can be reused across runs without clearing it.
+    fn does_loop(&self, seen: &mut [u32], stamp: u32) -> bool {
+        let mut guard = self.guard;
         loop {
-            let (end_point, done) = self.find_next_obstruction(guard.position, guard.direction);
-            for point in guard.move_to(end_point) {
-                visited.insert(point.position);
+            let state = self.state_index(guard);
+            if seen[state] == stamp {
+                return true;
             }
-            if visited_guards.contains(&guard) {
-                return (BTreeSet::new(), true);
-            }
-            visited_guards.insert(guard.clone());
-            if done {
-                break;
+            seen[state] = stamp;
+            match self.jumps[state] {
+                Some(next) => guard = next,
+                None => return false,
             }
         }
-        (visited, false)
     }
 
-    fn simulate_part1(&self) -> usize {
-        self.get_path_and_loop().0.len()
+    /// Every cell the guard walks through, or None if they never leave.
+    fn path(&self) -> Option<BTreeSet<Point>> {
+        let mut visited = BTreeSet::from([self.guard.position]);
+        let mut turns = BTreeSet::new();
+        let mut guard = self.guard;
+        while turns.insert(guard) {
+            let step = guard.direction.as_vector();
+            let walk = self.obstructions.ray_iter(guard.position, step);
+            match self.jumps[self.state_index(guard)] {
+                Some(next) => {
+                    visited.extend(walk.take_while(|p| *p != next.position + step));
+                    guard = next;
+                }
+                None => {
+                    visited.extend(walk);
+                    return Some(visited);
+                }
+            }
+        }
+        None
     }
 
-    fn does_loop(&self) -> bool {
-        self.get_path_and_loop().1
+    fn simulate_part1(&self) -> usize {
+        self.path().map(|p| p.len()).unwrap_or(0)
     }
 
     fn simulate_part2(&mut self) -> usize {
-        let mut loop_points = BTreeSet::<Point>::new();
+        let mut seen = vec![0; self.jumps.len()];
+        let mut loop_points = 0;
 
         // the only positions that matter are on the original path
-        let candidates = self.get_path_and_loop().0;
-        for candidate in candidates {
+        let candidates = self.path().unwrap_or_default();
+        for (stamp, candidate) in (1..).zip(candidates) {
             // can't plop something down directly on top of the guard, or in
             // front of them
             if candidate == self.guard.position {
                 continue;
             }
-            if candidate == self.guard.position + Point::new(0, -1) {
+            if candidate == self.guard.position + Direction::North {
                 continue;
             }
 
-            // simulate an obstruction there
-            self.obstructions.push(candidate);
-            // I bet you could do some kind of DP here to avoid resimulating
-            // the whole thing every time...
-            if self.does_loop() {
-                loop_points.insert(candidate);
+            let undo = self.patch(candidate);
+            if self.does_loop(&mut seen, stamp) {
+                loop_points += 1;
             }
-            self.obstructions.pop();
+            self.unpatch(undo);
         }
-        loop_points.len()
+        loop_points
     }
 }
 
@@ -204,7 +168,7 @@ fn main() -> anyhow::Result<()> {
     let mut stdin = std::io::stdin().lock();
     let mut s = String::new();
     stdin.read_to_string(&mut s)?;
-    let mut map = Map::from_str(&s);
+    let mut map = Map::from_str(&s)?;
     println!("part 1: {}", map.simulate_part1());
     println!("part 2: {}", map.simulate_part2());
     Ok(())
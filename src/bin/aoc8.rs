@@ -1,4 +1,4 @@
-use aoclib::{DenseGrid, Point};
+use aoclib::{CellChar, DenseGrid, Grid, Point};
 use itertools::Itertools;
 use std::collections::{BTreeMap, BTreeSet};
 use std::io::Read;
@@ -9,9 +9,19 @@ enum Cell {
     Antenna(char),
 }
 
-impl Default for Cell {
-    fn default() -> Self {
-        Self::Empty
+impl CellChar for Cell {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Cell::Empty),
+            other => Some(Cell::Antenna(other)),
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::Antenna(c) => *c,
+        }
     }
 }
 
@@ -26,17 +36,14 @@ impl Problem {
         let mut stdin = std::io::stdin().lock();
         let mut s = String::new();
         stdin.read_to_string(&mut s)?;
-        let grid = DenseGrid::from_input(&s, |c| match c {
-            '.' => Cell::Empty,
-            other => Cell::Antenna(other),
-        });
+        let grid = DenseGrid::<Cell>::parse(&s)?;
         let antennas = grid
             .iter()
             .filter_map(|(p, v)| match v {
                 Cell::Empty => None,
                 Cell::Antenna(v) => Some((p, v)),
             })
-            .map(|(p, v)| (p, v))
+            .map(|(p, v)| (p, *v))
             .collect::<Vec<_>>();
         Ok(Self { grid, antennas })
     }
@@ -76,18 +83,8 @@ fn part2(p: &Problem) -> usize {
         for (lhs, rhs) in points.iter().tuple_combinations() {
             let rhs = *rhs;
             let lhs = *lhs;
-            let right = rhs - lhs;
-            let mut test_point = rhs + right;
-            while p.grid.contains(test_point) {
-                antinodes.insert(test_point);
-                test_point = test_point + right;
-            }
-            let left = lhs - rhs;
-            let mut test_point = lhs + left;
-            while p.grid.contains(test_point) {
-                antinodes.insert(test_point);
-                test_point = test_point + left;
-            }
+            antinodes.extend(p.grid.ray_iter(rhs, rhs - lhs));
+            antinodes.extend(p.grid.ray_iter(lhs, lhs - rhs));
         }
     }
     antinodes.len()